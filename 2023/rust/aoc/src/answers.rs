@@ -0,0 +1,78 @@
+/// An answer recorded in a day's `answers.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    /// Stem of the input file the answer is for, e.g. `example` for `example.txt`.
+    pub input: String,
+    pub part: u8,
+    pub value: String,
+}
+
+/// Parses the content of an `answers.txt` file.
+///
+/// Each non-empty line that does not start with `#` must be of the form
+/// `<input file stem> <part> <answer>`.
+pub fn parse(text: &str) -> Result<Vec<Answer>, String> {
+    let mut answers: Vec<Answer> = vec![];
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 3 {
+            return Err(format!(
+                "Expected `<input> <part> <answer>` at line {}, but got '{line}'.",
+                i + 1
+            ));
+        }
+
+        let part = match fields[1] {
+            "1" => 1,
+            "2" => 2,
+            part => {
+                return Err(format!(
+                    "Expected part 1 or 2 at line {}, but got '{part}'.",
+                    i + 1
+                ))
+            }
+        };
+
+        answers.push(Answer {
+            input: fields[0].to_string(),
+            part,
+            value: fields[2].to_string(),
+        });
+    }
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = parse("# comment\n\nexample 1 142\ninput 2 54728\n").unwrap();
+        assert_eq!(
+            answers,
+            vec![
+                Answer {
+                    input: "example".into(),
+                    part: 1,
+                    value: "142".into()
+                },
+                Answer {
+                    input: "input".into(),
+                    part: 2,
+                    value: "54728".into()
+                },
+            ]
+        );
+
+        assert!(parse("example 3 142").is_err());
+        assert!(parse("example 1").is_err());
+    }
+}
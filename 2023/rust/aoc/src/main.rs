@@ -0,0 +1,268 @@
+use std::{env, fs, process::ExitCode};
+
+use common::{bench, input, Solver};
+use verify::Outcome;
+
+mod answers;
+mod verify;
+
+const YEAR: u16 = 2023;
+const USAGE: &str = "Usage: aoc [YEAR] [DAY] [PART] [--input PATH]
+       aoc verify [YEAR] [DAY] [PART]
+       aoc bench [YEAR] [DAY] [PART] [--input PATH] [--runs N] [--json]
+       aoc explain [YEAR] [DAY] [PART] [--input PATH]
+
+PATH can be `-` to read from standard input. Without `--input`, the path in
+the AOC_INPUT environment variable is used, with `{day}` replaced by the day.
+Explanations are coloured on a terminal, unless NO_COLOR is set.";
+
+fn solvers() -> Vec<Solver> {
+    vec![
+        Solver::new::<day1::Day1>(),
+        Solver::new::<day2::Day2>(),
+        Solver::new::<day3::Day3>(),
+        Solver::new::<day4::Day4>(),
+        Solver::new::<day5::Day5>(),
+        Solver::new::<day6::Day6>(),
+        Solver::new::<day7::Day7>(),
+    ]
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+enum Mode {
+    /// Print the answers for the selected days and parts.
+    #[default]
+    Run,
+    /// Check the answers against the ones recorded in each day's `answers.txt`.
+    Verify,
+    /// Time the parsing and each part of the selected days.
+    Bench,
+    /// Print how the selected days and parts reach their answers.
+    Explain,
+}
+
+#[derive(Debug, Default)]
+struct Args {
+    mode: Mode,
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    runs: Option<usize>,
+    json: bool,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut positionals: Vec<String> = vec![];
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| format!("Expected a path after '{arg}'."))?;
+                parsed.input = Some(path);
+            }
+            "--runs" | "-n" => {
+                let runs = args
+                    .next()
+                    .ok_or_else(|| format!("Expected a number of runs after '{arg}'."))?;
+                parsed.runs = Some(
+                    runs.parse()
+                        .ok()
+                        .filter(|&runs| runs > 0)
+                        .ok_or_else(|| format!("'{runs}' is not a valid number of runs."))?,
+                );
+            }
+            "--json" => parsed.json = true,
+            _ => positionals.push(arg),
+        }
+    }
+
+    let mut positionals = positionals.into_iter().peekable();
+    match positionals.peek().map(String::as_str) {
+        Some("verify") => parsed.mode = Mode::Verify,
+        Some("bench") => parsed.mode = Mode::Bench,
+        Some("explain") => parsed.mode = Mode::Explain,
+        _ => {}
+    }
+    if parsed.mode != Mode::Run {
+        positionals.next();
+    }
+    if let Some(year) = positionals.next() {
+        let year: u16 = year
+            .parse()
+            .map_err(|_| format!("'{year}' is not a valid year."))?;
+        if year != YEAR {
+            return Err(format!("Only {YEAR} is available, but got {year}."));
+        }
+    }
+    if let Some(day) = positionals.next() {
+        parsed.day = Some(
+            day.parse()
+                .map_err(|_| format!("'{day}' is not a valid day."))?,
+        );
+    }
+    if let Some(part) = positionals.next() {
+        match part.as_str() {
+            "1" => parsed.part = Some(1),
+            "2" => parsed.part = Some(2),
+            _ => return Err(format!("'{part}' is not a valid part, expected 1 or 2.")),
+        }
+    }
+    if let Some(arg) = positionals.next() {
+        return Err(format!("Unexpected argument '{arg}'."));
+    }
+
+    if parsed.input.is_some() && parsed.day.is_none() {
+        return Err("`--input` requires a day to be given.".into());
+    }
+    if parsed.input.is_some() && parsed.mode == Mode::Verify {
+        return Err("`--input` cannot be used with `verify`.".into());
+    }
+    if (parsed.runs.is_some() || parsed.json) && parsed.mode != Mode::Bench {
+        return Err("`--runs` and `--json` can only be used with `bench`.".into());
+    }
+
+    Ok(parsed)
+}
+
+fn select(solvers: &[Solver], day: Option<u8>) -> Result<Vec<&Solver>, String> {
+    match day {
+        Some(n) => Ok(vec![solvers
+            .iter()
+            .find(|solver| solver.day == n)
+            .ok_or_else(|| format!("Day {n} of {YEAR} is not solved yet."))?]),
+        None => Ok(solvers.iter().collect()),
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let solvers = solvers();
+
+    for solver in select(&solvers, args.day)? {
+        let input = input::read(solver.day, args.input.as_deref()).map_err(|e| e.to_string())?;
+
+        if args.part.is_none() || args.part == Some(1) {
+            let answer = (solver.part1)(&input).map_err(|e| e.to_string())?;
+            println!("{YEAR} day {} part 1: {answer}", solver.day);
+        }
+        if args.part.is_none() || args.part == Some(2) {
+            let answer = (solver.part2)(&input).map_err(|e| e.to_string())?;
+            println!("{YEAR} day {} part 2: {answer}", solver.day);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns whether every checked answer matched.
+fn verify(args: &Args) -> Result<bool, String> {
+    let solvers = solvers();
+    let (mut passed, mut failed, mut mismatched) = (0, 0, 0);
+
+    for solver in select(&solvers, args.day)? {
+        let path = input::day_dir(solver.day).join("answers.txt");
+        let answers = fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read '{}': {e}", path.display()))?;
+        let mut answers =
+            answers::parse(&answers).map_err(|e| format!("In '{}': {e}", path.display()))?;
+        if let Some(part) = args.part {
+            answers.retain(|answer| answer.part == part);
+        }
+
+        for check in verify::verify(solver, &answers, &input::day_dir(solver.day)) {
+            match check.outcome {
+                Outcome::Pass(_) => passed += 1,
+                Outcome::Mismatch { .. } => mismatched += 1,
+                Outcome::Fail(_) => failed += 1,
+            }
+            println!(
+                "{YEAR} day {} part {} ({}): {}",
+                check.day, check.part, check.input, check.outcome
+            );
+        }
+    }
+
+    println!("{passed} passed, {failed} failed, {mismatched} mismatched");
+
+    Ok(failed == 0 && mismatched == 0)
+}
+
+fn bench(args: &Args) -> Result<(), String> {
+    let solvers = solvers();
+    let mut options = bench::Options {
+        part: args.part,
+        ..Default::default()
+    };
+    if let Some(runs) = args.runs {
+        options.runs = runs;
+    }
+
+    let mut reports: Vec<bench::Report> = vec![];
+    for solver in select(&solvers, args.day)? {
+        let input = input::read(solver.day, args.input.as_deref()).map_err(|e| e.to_string())?;
+
+        let report = (solver.bench)(&input, &options).map_err(|e| e.to_string())?;
+        if !args.json {
+            println!("{YEAR} {report}");
+        }
+        reports.push(report);
+    }
+
+    if args.json {
+        let reports: Vec<String> = reports.iter().map(bench::Report::to_json).collect();
+        println!("[{}]", reports.join(","));
+    }
+
+    Ok(())
+}
+
+fn explain(args: &Args) -> Result<(), String> {
+    let solvers = solvers();
+
+    for solver in select(&solvers, args.day)? {
+        let input = input::read(solver.day, args.input.as_deref()).map_err(|e| e.to_string())?;
+
+        for part in [1, 2] {
+            if args.part.is_some_and(|p| p != part) {
+                continue;
+            }
+            match (solver.explain)(&input, part).map_err(|e| e.to_string())? {
+                Some(explanation) => {
+                    println!("{YEAR} day {} part {part}:\n{explanation}", solver.day)
+                }
+                None => println!("{YEAR} day {} part {part}: nothing to explain.", solver.day),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let res = match args.mode {
+        Mode::Run => run(&args).map(|()| true),
+        Mode::Verify => verify(&args),
+        Mode::Bench => bench(&args).map(|()| true),
+        Mode::Explain => explain(&args).map(|()| true),
+    };
+
+    match res {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::{any::Any, fmt, fs, panic, path::Path};
+
+use common::Solver;
+
+use crate::answers::Answer;
+
+#[derive(Debug)]
+pub enum Outcome {
+    Pass(String),
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The solver could not produce an answer at all.
+    Fail(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass(value) => write!(f, "pass, {value}"),
+            Outcome::Mismatch { expected, actual } => {
+                write!(f, "MISMATCH, expected {expected}, got {actual}")
+            }
+            Outcome::Fail(reason) => write!(f, "FAIL, {reason}"),
+        }
+    }
+}
+
+/// The result of checking one recorded answer.
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub outcome: Outcome,
+}
+
+/// Runs `solver` on every input `answers` refers to, looking for the input
+/// files in `dir`, and compares what it returns with the recorded answers.
+pub fn verify(solver: &Solver, answers: &[Answer], dir: &Path) -> Vec<Check> {
+    answers
+        .iter()
+        .map(|answer| Check {
+            day: solver.day,
+            part: answer.part,
+            input: answer.input.clone(),
+            outcome: check(solver, answer, dir),
+        })
+        .collect()
+}
+
+fn check(solver: &Solver, answer: &Answer, dir: &Path) -> Outcome {
+    let path = dir.join(format!("{}.txt", answer.input));
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(e) => return Outcome::Fail(format!("could not read '{}': {e}", path.display())),
+    };
+
+    let part = match answer.part {
+        1 => solver.part1,
+        _ => solver.part2,
+    };
+
+    // Silence the default hook so that a panicking solver only shows up in the report.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let actual = panic::catch_unwind(|| part(&input));
+    panic::set_hook(hook);
+
+    match actual {
+        Ok(Ok(actual)) if actual == answer.value => Outcome::Pass(actual),
+        Ok(Ok(actual)) => Outcome::Mismatch {
+            expected: answer.value.clone(),
+            actual,
+        },
+        Ok(Err(e)) => Outcome::Fail(e.to_string()),
+        Err(payload) => Outcome::Fail(format!("panicked: {}", panic_message(&payload))),
+    }
+}
+
+fn panic_message(payload: &Box<dyn Any + Send>) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "<unknown panic payload>"
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Solution};
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// How many times each part is solved.
+    pub runs: usize,
+    /// Only bench this part, or both if `None`.
+    pub part: Option<u8>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            runs: 100,
+            part: None,
+        }
+    }
+}
+
+/// Statistics over the durations of several runs of the same part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timings {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    fn new(mut durations: Vec<Duration>) -> Timings {
+        assert!(!durations.is_empty(), "Expected at least one run.");
+        durations.sort_unstable();
+        Timings {
+            runs: durations.len(),
+            min: durations[0],
+            median: durations[durations.len() / 2],
+            max: durations[durations.len() - 1],
+        }
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            r#"{{"runs":{},"min_ns":{},"median_ns":{},"max_ns":{}}}"#,
+            self.runs,
+            self.min.as_nanos(),
+            self.median.as_nanos(),
+            self.max.as_nanos()
+        )
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, max {:?} ({} runs)",
+            self.min, self.median, self.max, self.runs
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub parse: Duration,
+    pub part1: Option<Timings>,
+    pub part2: Option<Timings>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        let part = |timings: &Option<Timings>| match timings {
+            Some(timings) => timings.to_json(),
+            None => "null".to_string(),
+        };
+        format!(
+            r#"{{"day":{},"parse_ns":{},"part1":{},"part2":{}}}"#,
+            self.day,
+            self.parse.as_nanos(),
+            part(&self.part1),
+            part(&self.part2)
+        )
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {}: parse {:?}", self.day, self.parse)?;
+        if let Some(ref timings) = self.part1 {
+            write!(f, "\n  part 1: {timings}")?;
+        }
+        if let Some(ref timings) = self.part2 {
+            write!(f, "\n  part 2: {timings}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses `input` once, then solves the selected parts `options.runs` times each.
+///
+/// A part failing to solve is reported before any timing, rather than timed.
+pub fn bench<S: Solution>(input: &str, options: &Options) -> Result<Report, Error> {
+    let start = Instant::now();
+    let parsed = black_box(S::parse(black_box(input))?);
+    let parse = start.elapsed();
+
+    let bench_part1 = matches!(options.part, None | Some(1));
+    let bench_part2 = matches!(options.part, None | Some(2));
+    if bench_part1 {
+        S::solve_part1(&parsed)?;
+    }
+    if bench_part2 {
+        S::solve_part2(&parsed)?;
+    }
+
+    let time = |solve: &dyn Fn()| {
+        let durations = (0..options.runs.max(1))
+            .map(|_| {
+                let start = Instant::now();
+                solve();
+                start.elapsed()
+            })
+            .collect();
+        Timings::new(durations)
+    };
+
+    let part1 = bench_part1.then(|| {
+        time(&|| {
+            let _ = black_box(S::solve_part1(black_box(&parsed)));
+        })
+    });
+    let part2 = bench_part2.then(|| {
+        time(&|| {
+            let _ = black_box(S::solve_part2(black_box(&parsed)));
+        })
+    });
+
+    Ok(Report {
+        day: S::DAY,
+        parse,
+        part1,
+        part2,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timings() {
+        let timings = Timings::new(vec![
+            Duration::from_nanos(30),
+            Duration::from_nanos(10),
+            Duration::from_nanos(20),
+        ]);
+        assert_eq!(
+            timings,
+            Timings {
+                runs: 3,
+                min: Duration::from_nanos(10),
+                median: Duration::from_nanos(20),
+                max: Duration::from_nanos(30),
+            }
+        );
+        assert_eq!(
+            timings.to_json(),
+            r#"{"runs":3,"min_ns":10,"median_ns":20,"max_ns":30}"#
+        );
+    }
+}
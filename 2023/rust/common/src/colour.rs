@@ -0,0 +1,47 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+/// Environment variable turning colours off when set, see <https://no-color.org>.
+pub const NO_COLOR: &str = "NO_COLOR";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Cyan,
+}
+
+impl Colour {
+    fn ansi_code(self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Cyan => 36,
+        }
+    }
+}
+
+/// Whether what is printed should be coloured: only on a terminal, and when
+/// [`NO_COLOR`] is not set.
+pub fn enabled() -> bool {
+    io::stdout().is_terminal() && env::var_os(NO_COLOR).is_none_or(|value| value.is_empty())
+}
+
+/// `text` in `colour`, for a terminal.
+pub fn paint(text: &str, colour: Colour) -> String {
+    format!("\x1b[{}m{text}\x1b[0m", colour.ansi_code())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(paint("42", Colour::Green), "\x1b[32m42\x1b[0m");
+    }
+}
@@ -0,0 +1,202 @@
+use std::{error, fmt, ops::Range, str::FromStr};
+
+/// An error in the puzzle input, pointing at where it is.
+///
+/// Rendered with an excerpt of the faulty line, the offending part of it
+/// underlined with carets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based number of the line.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The whole faulty line.
+    pub excerpt: String,
+    /// The offending part of the line, empty if something is missing.
+    pub snippet: String,
+    /// What was expected instead, e.g. "a colour".
+    pub expected: String,
+}
+
+impl ParseError {
+    /// `line_idx` is the 0-based index of `line` in the input, and `span` the
+    /// byte range of the offending part of `line`.
+    pub fn new(
+        day: u8,
+        line_idx: usize,
+        line: &str,
+        span: Range<usize>,
+        expected: impl Into<String>,
+    ) -> ParseError {
+        ParseError {
+            day,
+            line: line_idx + 1,
+            column: line[..span.start].chars().count() + 1,
+            excerpt: line.to_string(),
+            snippet: line[span].to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error about the whole of `line`.
+    pub fn line(day: u8, line_idx: usize, line: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(day, line_idx, line, 0..line.len(), expected)
+    }
+
+    /// An error about lines missing at the end of an input of `n_lines` lines.
+    pub fn missing_line(day: u8, n_lines: usize, expected: impl Into<String>) -> ParseError {
+        ParseError::new(day, n_lines, "", 0..0, expected)
+    }
+
+    /// Parses the number at `span` in `line`.
+    pub fn number<T: FromStr>(
+        day: u8,
+        line_idx: usize,
+        line: &str,
+        span: Range<usize>,
+    ) -> Result<T, ParseError> {
+        line[span.clone()].parse().map_err(|_| {
+            ParseError::new(
+                day,
+                line_idx,
+                line,
+                span,
+                format!("a number fitting in {}", std::any::type_name::<T>()),
+            )
+        })
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid input for day {} at line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        if self.snippet.is_empty() {
+            writeln!(f, ".")?;
+        } else {
+            writeln!(f, ", found '{}'.", self.snippet)?;
+        }
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.excerpt)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.snippet.chars().count().max(1))
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// A valid puzzle input that a part cannot give an answer for, like one whose
+/// answer is too big to count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    pub day: u8,
+    pub part: u8,
+    /// Why there is no answer.
+    pub reason: String,
+}
+
+impl SolveError {
+    pub fn new(day: u8, part: u8, reason: impl Into<String>) -> SolveError {
+        SolveError {
+            day,
+            part,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "No answer for day {} part {}: {}",
+            self.day, self.part, self.reason
+        )
+    }
+}
+
+impl error::Error for SolveError {}
+
+/// Why a part gave no answer for an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => e.fmt(f),
+            Error::Solve(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::Solve(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Error {
+        Error::Parse(e)
+    }
+}
+
+impl From<SolveError> for Error {
+    fn from(e: SolveError) -> Error {
+        Error::Solve(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let line = "Game 3: 8 green, 6 purple";
+        let err = ParseError::new(2, 2, line, 19..25, "a colour");
+        assert_eq!(err.column, 20);
+        assert_eq!(
+            err.to_string(),
+            "Invalid input for day 2 at line 3, column 20: expected a colour, found 'purple'.
+  |
+3 | Game 3: 8 green, 6 purple
+  |                    ^^^^^^"
+        );
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(ParseError::number::<u32>(1, 0, "a 42 b", 2..4), Ok(42));
+
+        let err = ParseError::number::<u8>(1, 0, "a 420 b", 2..5).unwrap_err();
+        assert_eq!(err.snippet, "420");
+        assert_eq!(err.expected, "a number fitting in u8");
+    }
+
+    #[test]
+    fn test_solve_error() {
+        let err: Error = SolveError::new(4, 2, "Card 65 has too many copies.").into();
+        assert_eq!(
+            err.to_string(),
+            "No answer for day 4 part 2: Card 65 has too many copies."
+        );
+    }
+}
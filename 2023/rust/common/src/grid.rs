@@ -0,0 +1,149 @@
+use std::ops::Range;
+
+/// A position in a grid, as `(row, column)`.
+pub type Pos = (usize, usize);
+
+/// A rectangle of cells, stored row after row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// `cells` are given row after row, `width` of them per row.
+    pub fn new(cells: Vec<T>, width: usize) -> Grid<T> {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert!(
+            cells.len() == width * height,
+            "Expected rows of {width} cells, but got {} cells.",
+            cells.len()
+        );
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Rows shorter than the longest one are padded with `fill`.
+    pub fn from_rows<R: IntoIterator<Item = T>>(
+        rows: impl IntoIterator<Item = R>,
+        fill: T,
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let rows: Vec<Vec<T>> = rows
+            .into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = if width == 0 { 0 } else { rows.len() };
+
+        let mut cells: Vec<T> = Vec::with_capacity(width * rows.len());
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.extend(row);
+        }
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        if row < self.height && col < self.width {
+            Some(&self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, and there are no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "Column {col} is out of the grid.");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// The cells of `row` in the columns `cols`.
+    pub fn span(&self, row: usize, cols: Range<usize>) -> &[T] {
+        &self.row(row)[cols]
+    }
+
+    /// The positions of the (up to) 8 cells around `pos`.
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.span_neighbours(pos.0, pos.1..pos.1 + 1)
+    }
+
+    /// The positions of the cells around the span of `row` in the columns `cols`,
+    /// row above first, from left to right.
+    pub fn span_neighbours(
+        &self,
+        row: usize,
+        cols: Range<usize>,
+    ) -> impl Iterator<Item = Pos> + '_ {
+        let rows = row.saturating_sub(1)..(row + 2).min(self.height);
+        let around = cols.start.saturating_sub(1)..(cols.end + 1).min(self.width);
+        rows.flat_map(move |r| around.clone().map(move |c| (r, c)))
+            .filter(move |&(r, c)| r != row || !cols.contains(&c))
+    }
+
+    /// All the positions, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.height).flat_map(move |row| (0..self.width).map(move |col| (row, col)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::from_rows(["abc".chars(), "de".chars(), "fgh".chars()], '.');
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.row(1), ['d', 'e', '.']);
+        assert_eq!(grid.column(2).collect::<String>(), "c.h");
+        assert_eq!(grid.span(2, 1..3), ['g', 'h']);
+        assert_eq!(grid.get((2, 0)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.rows().count(), 3);
+
+        let neighbours: String = grid
+            .neighbours((0, 0))
+            .map(|pos| grid.get(pos).unwrap())
+            .collect();
+        assert_eq!(neighbours, "bde");
+        assert_eq!(grid.neighbours((1, 1)).count(), 8);
+        assert_eq!(grid.span_neighbours(1, 0..3).count(), 6);
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid: Grid<char> = Grid::from_rows(Vec::<Vec<char>>::new(), '.');
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.positions().count(), 0);
+    }
+}
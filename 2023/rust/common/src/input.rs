@@ -0,0 +1,148 @@
+use std::{
+    env, error, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable giving the path of the input, where `{day}` is
+/// replaced by the number of the day.
+pub const ENV_VAR: &str = "AOC_INPUT";
+
+/// Name given on the command line to read the input from standard input.
+pub const STDIN: &str = "-";
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound { day: u8, tried: Vec<PathBuf> },
+    Read { path: PathBuf, source: io::Error },
+    Stdin(io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { day, tried } => {
+                writeln!(f, "Could not find the input of day {day}. Tried:")?;
+                for path in tried {
+                    writeln!(f, "  - {}", path.display())?;
+                }
+                write!(
+                    f,
+                    "Give the path of the input, set `{ENV_VAR}`, or use `{STDIN}` to read from standard input."
+                )
+            }
+            InputError::Read { path, source } => {
+                write!(f, "Could not read '{}': {source}", path.display())
+            }
+            InputError::Stdin(source) => write!(f, "Could not read standard input: {source}"),
+        }
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            InputError::NotFound { .. } => None,
+            InputError::Read { source, .. } | InputError::Stdin(source) => Some(source),
+        }
+    }
+}
+
+/// The directory holding a day's inputs in this workspace.
+pub fn day_dir(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("The `common` crate should be inside the workspace.")
+        .join(format!("day{day}"))
+        .join("src")
+}
+
+/// Where to look for the input of `day` when none is given.
+fn default_paths(day: u8) -> Vec<PathBuf> {
+    let crate_name = format!("day{day}");
+    let mut paths: Vec<PathBuf> = vec![];
+    // From the directory of the day's crate, and only that day's, as
+    // `src/input.txt` would be another day's input anywhere else
+    if env::current_dir().is_ok_and(|dir| dir.ends_with(&crate_name)) {
+        paths.push(PathBuf::from("src").join("input.txt"));
+    }
+    // From the root of the workspace
+    paths.push(PathBuf::from(&crate_name).join("src").join("input.txt"));
+    paths.push(day_dir(day).join("input.txt"));
+
+    paths
+}
+
+/// Reads the input of `day`.
+///
+/// `arg` is the input given on the command line, either a path or [`STDIN`].
+/// If it is `None`, the [`ENV_VAR`] environment variable is used in the same
+/// way, and failing that, the first of the default locations that exists.
+pub fn read(day: u8, arg: Option<&str>) -> Result<String, InputError> {
+    let given = match arg {
+        Some(arg) => Some(arg.to_string()),
+        None => env::var(ENV_VAR)
+            .ok()
+            .map(|path| path.replace("{day}", &day.to_string())),
+    };
+
+    let candidates = match given.as_deref() {
+        Some(STDIN) => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(InputError::Stdin)?;
+            return Ok(input);
+        }
+        Some(path) => vec![PathBuf::from(path)],
+        None => default_paths(day),
+    };
+
+    for path in &candidates {
+        match fs::read_to_string(path) {
+            Ok(input) => return Ok(input),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(source) => {
+                return Err(InputError::Read {
+                    path: path.clone(),
+                    source,
+                })
+            }
+        }
+    }
+
+    Err(InputError::NotFound {
+        day,
+        tried: candidates,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_found_lists_tried_paths() {
+        let err = read(1, Some("does/not/exist.txt")).unwrap_err();
+        match err {
+            InputError::NotFound { day, ref tried } => {
+                assert_eq!(day, 1);
+                assert_eq!(tried, &vec![PathBuf::from("does/not/exist.txt")]);
+            }
+            _ => panic!("Expected `InputError::NotFound`, got {err:?}."),
+        }
+        assert!(err.to_string().contains("  - does/not/exist.txt"));
+    }
+
+    #[test]
+    fn test_default_paths() {
+        // Tests run from the directory of the `common` crate
+        for path in default_paths(3) {
+            assert!(
+                path.to_string_lossy().contains("day3"),
+                "{}",
+                path.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::{env, fmt::Display, process::ExitCode};
+
+pub mod bench;
+pub mod colour;
+mod error;
+pub mod grid;
+pub mod input;
+
+pub use error::{Error, ParseError, SolveError};
+
+/// The solver of one day of the calendar.
+///
+/// Both parts take the whole puzzle input as text and return a typed answer.
+/// Parsing is kept apart from solving so that the two can be timed separately,
+/// and solving fails with a [`SolveError`] on inputs that parse but have no
+/// answer.
+pub trait Solution {
+    /// The day of December the puzzle was released on.
+    const DAY: u8;
+
+    /// What both parts work on once the input is parsed.
+    type Parsed;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn solve_part1(parsed: &Self::Parsed) -> Result<Self::Answer1, SolveError>;
+    fn solve_part2(parsed: &Self::Parsed) -> Result<Self::Answer2, SolveError>;
+
+    fn part1(input: &str) -> Result<Self::Answer1, Error> {
+        Ok(Self::solve_part1(&Self::parse(input)?)?)
+    }
+
+    fn part2(input: &str) -> Result<Self::Answer2, Error> {
+        Ok(Self::solve_part2(&Self::parse(input)?)?)
+    }
+
+    /// A report of how `part` reaches its answer, for the days that have one.
+    fn explain(_parsed: &Self::Parsed, _part: u8) -> Option<String> {
+        None
+    }
+}
+
+/// A [`Solution`] with its answer types erased, so that days with
+/// different answer types can be stored and dispatched to together.
+#[derive(Debug, Clone, Copy)]
+pub struct Solver {
+    pub day: u8,
+    pub part1: fn(&str) -> Result<String, Error>,
+    pub part2: fn(&str) -> Result<String, Error>,
+    pub bench: fn(&str, &bench::Options) -> Result<bench::Report, Error>,
+    pub explain: fn(&str, u8) -> Result<Option<String>, ParseError>,
+}
+
+impl Solver {
+    pub fn new<S: Solution>() -> Solver {
+        Solver {
+            day: S::DAY,
+            part1: erased_part1::<S>,
+            part2: erased_part2::<S>,
+            bench: bench::bench::<S>,
+            explain: erased_explain::<S>,
+        }
+    }
+}
+
+fn erased_part1<S: Solution>(input: &str) -> Result<String, Error> {
+    Ok(S::part1(input)?.to_string())
+}
+
+fn erased_part2<S: Solution>(input: &str) -> Result<String, Error> {
+    Ok(S::part2(input)?.to_string())
+}
+
+fn erased_explain<S: Solution>(input: &str, part: u8) -> Result<Option<String>, ParseError> {
+    Ok(S::explain(&S::parse(input)?, part))
+}
+
+/// Entry point of the `dayN-partM` binaries.
+///
+/// The input is read as described in [`input::read`], from the first argument
+/// if there is one, and the answer to `part` is printed.
+pub fn run_part<S: Solution>(part: u8) -> ExitCode {
+    let arg = env::args().nth(1);
+    let answer = input::read(S::DAY, arg.as_deref())
+        .map_err(|e| e.to_string())
+        .and_then(|input| {
+            match part {
+                1 => S::part1(&input).map(|answer| answer.to_string()),
+                _ => S::part2(&input).map(|answer| answer.to_string()),
+            }
+            .map_err(|e| e.to_string())
+        });
+
+    print_answer(answer)
+}
+
+/// Entry point of the binaries of the days taking more than the input from
+/// the command line.
+///
+/// The arguments, without the name of the program, go through `parse_args`,
+/// whose errors are printed along with `usage`. What `answer` then returns for
+/// them is printed.
+pub fn run_with<A>(
+    usage: &str,
+    parse_args: impl FnOnce(env::Args) -> Result<A, String>,
+    answer: impl FnOnce(&A) -> Result<String, String>,
+) -> ExitCode {
+    let mut args = env::args();
+    args.next();
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}\n{usage}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    print_answer(answer(&args))
+}
+
+/// Prints the answer, or the error to standard error, failing.
+fn print_answer(answer: Result<String, String>) -> ExitCode {
+    match answer {
+        Ok(answer) => {
+            println!("{answer}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
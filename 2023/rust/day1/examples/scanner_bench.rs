@@ -0,0 +1,70 @@
+//! Compares the scanner with trying every word at every position, on a few
+//! megabytes of input made of the puzzle input repeated.
+//!
+//! Run with `cargo run --release -p day1 --example scanner_bench [MEGABYTES]`.
+
+use std::{
+    env,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use day1::calibration::{Locale, Vocabulary};
+
+const RUNS: usize = 10;
+
+/// The median duration of `RUNS` runs of `f`, with what it returned.
+fn time(f: impl Fn() -> u32) -> (Duration, u32) {
+    let mut durations: Vec<Duration> = vec![];
+    let mut result = 0;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        result = black_box(f());
+        durations.push(start.elapsed());
+    }
+    durations.sort_unstable();
+
+    (durations[RUNS / 2], result)
+}
+
+fn main() {
+    let megabytes: usize = env::args()
+        .nth(1)
+        .map(|arg| arg.parse().expect("Expected a number of megabytes."))
+        .unwrap_or(8);
+
+    let input = include_str!("../src/input.txt");
+    let repeats = (megabytes << 20).div_ceil(input.len());
+    let lines: Vec<&str> = input
+        .lines()
+        .cycle()
+        .take(repeats * input.lines().count())
+        .collect();
+    println!(
+        "{} lines, {} MB",
+        lines.len(),
+        lines.iter().map(|line| line.len() + 1).sum::<usize>() >> 20
+    );
+
+    for (name, vocabulary) in [
+        ("digits", Vocabulary::digits()),
+        ("english", Vocabulary::english()),
+        (
+            "english and german, 0 to 20",
+            Vocabulary::digits()
+                .with_spelled(Locale::English, 0..=20)
+                .with_spelled(Locale::German, 0..=20),
+        ),
+    ] {
+        let (naive, naive_sum) = time(|| {
+            lines
+                .iter()
+                .map(|line| vocabulary.naive_calibration_value(line))
+                .sum()
+        });
+        let (scanner, scanner_sum) = time(|| vocabulary.calibration_sum(&lines));
+        assert_eq!(naive_sum, scanner_sum);
+
+        println!("{name}: naive {naive:?}, scanner {scanner:?}");
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+
+/// Where a pattern was found, as byte offsets in the text scanned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// The index of the pattern found.
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// An Aho-Corasick automaton, finding any of several patterns in a single
+/// pass over a text.
+///
+/// The failure links are compiled into a full transition table, so each byte
+/// of the text costs a single lookup.
+#[derive(Debug, Clone)]
+pub struct Automaton {
+    /// For each state, the state reached on each byte.
+    transitions: Vec<[u32; 256]>,
+    /// For each state, the length of the pattern prefix it stands for.
+    depths: Vec<usize>,
+    /// For each state, the pattern it is the end of, if any.
+    patterns: Vec<Option<usize>>,
+    /// For each state, the longest proper suffix of its prefix that is a
+    /// pattern, as the state that is its end.
+    dictionary: Vec<Option<usize>>,
+    pattern_lens: Vec<usize>,
+}
+
+impl Automaton {
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> Automaton {
+        let mut automaton = Automaton {
+            transitions: vec![[0; 256]],
+            depths: vec![0],
+            patterns: vec![None],
+            dictionary: vec![None],
+            pattern_lens: vec![],
+        };
+
+        // The trie of the patterns, state 0 being the root, which no byte leads
+        // back to, so that 0 means "no child" until the failure links are in
+        for (p, pattern) in patterns.iter().enumerate() {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "Patterns cannot be empty.");
+
+            let mut state = 0;
+            for &b in pattern {
+                if automaton.transitions[state][b as usize] == 0 {
+                    automaton.transitions[state][b as usize] = automaton.depths.len() as u32;
+                    automaton.transitions.push([0; 256]);
+                    automaton.depths.push(automaton.depths[state] + 1);
+                    automaton.patterns.push(None);
+                    automaton.dictionary.push(None);
+                }
+                state = automaton.transitions[state][b as usize] as usize;
+            }
+            // The first of duplicate patterns wins
+            automaton.patterns[state].get_or_insert(p);
+            automaton.pattern_lens.push(pattern.len());
+        }
+
+        // Breadth first, so that the failure state of a state, being shallower,
+        // is complete by the time the state is reached
+        let mut failures: Vec<usize> = vec![0; automaton.depths.len()];
+        let mut queue: VecDeque<usize> = VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            for b in 0..256 {
+                let child = automaton.transitions[state][b] as usize;
+                let fallback = if state == 0 {
+                    0
+                } else {
+                    automaton.transitions[failures[state]][b] as usize
+                };
+
+                if child == 0 {
+                    automaton.transitions[state][b] = fallback as u32;
+                    continue;
+                }
+                failures[child] = fallback;
+                automaton.dictionary[child] = if automaton.patterns[fallback].is_some() {
+                    Some(fallback)
+                } else {
+                    automaton.dictionary[fallback]
+                };
+                queue.push_back(child);
+            }
+        }
+
+        automaton
+    }
+
+    /// The patterns ending at `state`, the longest first.
+    fn outputs(&self, state: usize) -> impl Iterator<Item = usize> + '_ {
+        let own = self.patterns[state].map(|_| state);
+        std::iter::successors(own.or(self.dictionary[state]), |&s| self.dictionary[s])
+            .filter_map(|s| self.patterns[s])
+    }
+
+    /// All the matches of `text`, even overlapping ones, by end then from the
+    /// longest.
+    pub fn overlapping(&self, text: impl IntoIterator<Item = u8>) -> Vec<Match> {
+        let mut matches: Vec<Match> = vec![];
+        let mut state = 0;
+
+        for (i, b) in text.into_iter().enumerate() {
+            state = self.transitions[state][b as usize] as usize;
+            matches.extend(self.outputs(state).map(|pattern| Match {
+                pattern,
+                start: i + 1 - self.pattern_lens[pattern],
+                end: i + 1,
+            }));
+        }
+
+        matches
+    }
+
+    /// The match of `text` starting first, the longest among those.
+    pub fn leftmost_longest(&self, text: impl IntoIterator<Item = u8>) -> Option<Match> {
+        let mut best: Option<Match> = None;
+        let mut state = 0;
+
+        for (i, b) in text.into_iter().enumerate() {
+            state = self.transitions[state][b as usize] as usize;
+            let end = i + 1;
+
+            if let Some(pattern) = self.outputs(state).next() {
+                let start = end - self.pattern_lens[pattern];
+                // Ending later at the same start means longer
+                if best.is_none_or(|best| start <= best.start) {
+                    best = Some(Match {
+                        pattern,
+                        start,
+                        end,
+                    });
+                }
+            }
+
+            // Any match still to come starts at the prefix being read at best
+            if best.is_some_and(|best| end - self.depths[state] > best.start) {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let automaton = Automaton::new(&["abcd", "bc", "b", "cde", "e"]);
+        let find = |text: &str| automaton.leftmost_longest(text.bytes());
+
+        assert_eq!(
+            find("xabcde"),
+            Some(Match {
+                pattern: 0,
+                start: 1,
+                end: 5
+            })
+        );
+        assert_eq!(find("xbcde").map(|m| m.pattern), Some(1));
+        assert_eq!(find("xbxe").map(|m| m.pattern), Some(2));
+        assert_eq!(find("abce").map(|m| m.pattern), Some(1));
+        assert_eq!(find("cdxe").map(|m| (m.pattern, m.start)), Some((4, 3)));
+        assert_eq!(find("xyz"), None);
+        assert_eq!(find(""), None);
+
+        let matches: Vec<(usize, usize)> = automaton
+            .overlapping("abcde".bytes())
+            .iter()
+            .map(|m| (m.pattern, m.start))
+            .collect();
+        assert_eq!(matches, [(2, 1), (1, 1), (0, 0), (3, 2), (4, 4)]);
+    }
+}
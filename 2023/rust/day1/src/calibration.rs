@@ -0,0 +1,318 @@
+use std::{cmp::Reverse, ops::RangeInclusive};
+
+use crate::automaton::{Automaton, Match};
+
+/// A language numbers can be spelled out in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    French,
+    German,
+}
+
+impl Locale {
+    /// The words for the numbers from 0 to 20, in order.
+    fn number_words(self) -> [&'static str; 21] {
+        match self {
+            Locale::English => [
+                "zero",
+                "one",
+                "two",
+                "three",
+                "four",
+                "five",
+                "six",
+                "seven",
+                "eight",
+                "nine",
+                "ten",
+                "eleven",
+                "twelve",
+                "thirteen",
+                "fourteen",
+                "fifteen",
+                "sixteen",
+                "seventeen",
+                "eighteen",
+                "nineteen",
+                "twenty",
+            ],
+            Locale::French => [
+                "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+                "dix", "onze", "douze", "treize", "quatorze", "quinze", "seize", "dix-sept",
+                "dix-huit", "dix-neuf", "vingt",
+            ],
+            Locale::German => [
+                "null",
+                "eins",
+                "zwei",
+                "drei",
+                "vier",
+                "fünf",
+                "sechs",
+                "sieben",
+                "acht",
+                "neun",
+                "zehn",
+                "elf",
+                "zwölf",
+                "dreizehn",
+                "vierzehn",
+                "fünfzehn",
+                "sechzehn",
+                "siebzehn",
+                "achtzehn",
+                "neunzehn",
+                "zwanzig",
+            ],
+        }
+    }
+
+    /// The word for `n`, if it is at most 20.
+    pub fn word(self, n: u32) -> Option<&'static str> {
+        self.number_words().get(n as usize).copied()
+    }
+}
+
+/// The words standing for digits in a line of the calibration document.
+///
+/// A word standing for a number of several digits, like "ten", stands for
+/// all of them in order, so "ten" is a 1 followed by a 0.
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    /// The words and the digits they stand for, the longest words first, so
+    /// that the first word found at a position is the longest one there.
+    words: Vec<(String, String)>,
+}
+
+impl Vocabulary {
+    /// A vocabulary with no words at all.
+    pub fn new() -> Vocabulary {
+        Vocabulary::default()
+    }
+
+    /// The digits themselves, as in part 1.
+    pub fn digits() -> Vocabulary {
+        Vocabulary::new().with_digits()
+    }
+
+    /// The digits and the words for 1 to 9, as in part 2.
+    pub fn english() -> Vocabulary {
+        Vocabulary::digits().with_spelled(Locale::English, 1..=9)
+    }
+
+    pub fn with_digits(self) -> Vocabulary {
+        (0..=9).fold(self, |vocabulary, n| {
+            vocabulary.with_word(&n.to_string(), n)
+        })
+    }
+
+    /// Adds the words of `locale` for the numbers in `numbers`, which can go
+    /// from 0 to 20.
+    pub fn with_spelled(self, locale: Locale, numbers: RangeInclusive<u32>) -> Vocabulary {
+        numbers.fold(self, |vocabulary, n| {
+            let word = locale
+                .word(n)
+                .unwrap_or_else(|| panic!("There is no word for {n} in {locale:?}."));
+            vocabulary.with_word(word, n)
+        })
+    }
+
+    /// Adds `word`, standing for the digits of `value`, replacing what it
+    /// stood for if it was already there.
+    pub fn with_word(mut self, word: &str, value: u32) -> Vocabulary {
+        assert!(!word.is_empty(), "Words cannot be empty.");
+        self.words.retain(|(w, _)| w != word);
+        let i = self.words.partition_point(|(w, _)| w.len() >= word.len());
+        self.words.insert(i, (word.to_string(), value.to_string()));
+        self
+    }
+
+    /// Compiles the vocabulary into a [`Scanner`].
+    pub fn scanner(&self) -> Scanner {
+        Scanner::new(self)
+    }
+
+    pub fn calibration_sum<S: AsRef<str>>(&self, lines: &[S]) -> u32 {
+        let scanner = self.scanner();
+        lines
+            .iter()
+            .map(|line| scanner.calibration_value(line.as_ref()))
+            .sum()
+    }
+
+    /// The same as [`Scanner::calibration_value`], but trying every word at
+    /// every position, as a reference for the scanner.
+    pub fn naive_calibration_value(&self, line: &str) -> u32 {
+        // The words are sorted the longest first
+        let first = line.char_indices().find_map(|(i, _)| {
+            self.words
+                .iter()
+                .find(|(word, _)| line[i..].starts_with(word.as_str()))
+        });
+        let last = line
+            .char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .rev()
+            .find_map(|end| {
+                self.words
+                    .iter()
+                    .find(|(word, _)| line[..end].ends_with(word.as_str()))
+            });
+
+        match (first, last) {
+            (Some((_, first)), Some((_, last))) => calibration_value(first, last),
+            _ => 0,
+        }
+    }
+}
+
+/// The first digit of `first` and the last of `last` making a two-digit number.
+fn calibration_value(first: &str, last: &str) -> u32 {
+    let digit = |c: Option<char>| c.and_then(|c| c.to_digit(10)).unwrap_or(0);
+    digit(first.chars().next()) * 10 + digit(last.chars().last())
+}
+
+/// A [`Vocabulary`] compiled into automata, finding the first word of a line
+/// in one forward pass, and the last one in one backward pass.
+///
+/// The first word is the one starting first, and the last word the one ending
+/// last, the longest among those in both cases. Words can overlap, so
+/// "eightwo" starts with an 8 and ends with a 2, while "dreizehn" is a 13 both
+/// ways, whether "drei" and "zehn" are words too or not.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    forward: Automaton,
+    /// The same as `forward`, with the words reversed.
+    backward: Automaton,
+    /// The digits each word stands for, in the order of the patterns.
+    digits: Vec<String>,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Scanner {
+        let (words, digits): (Vec<&str>, Vec<String>) = vocabulary
+            .words
+            .iter()
+            .map(|(word, digits)| (word.as_str(), digits.clone()))
+            .unzip();
+        let reversed: Vec<Vec<u8>> = words
+            .iter()
+            .map(|word| word.bytes().rev().collect())
+            .collect();
+
+        Scanner {
+            forward: Automaton::new(&words),
+            backward: Automaton::new(&reversed),
+            digits,
+        }
+    }
+
+    pub fn first_word(&self, line: &str) -> Option<Match> {
+        self.forward.leftmost_longest(line.bytes())
+    }
+
+    pub fn last_word(&self, line: &str) -> Option<Match> {
+        // Read backwards, the first word ending last is the first to start
+        let len = line.len();
+        self.backward
+            .leftmost_longest(line.bytes().rev())
+            .map(|m| Match {
+                pattern: m.pattern,
+                start: len - m.end,
+                end: len - m.start,
+            })
+    }
+
+    /// All the words of `line`, even overlapping ones, by start then from the
+    /// longest.
+    pub fn words(&self, line: &str) -> Vec<Match> {
+        let mut words = self.forward.overlapping(line.bytes());
+        words.sort_unstable_by_key(|m| (m.start, Reverse(m.end)));
+        words
+    }
+
+    /// The digits the word found at `m` stands for.
+    pub fn digits(&self, m: &Match) -> &str {
+        &self.digits[m.pattern]
+    }
+
+    /// The first and last digits of `line` making a two-digit number, or 0 if
+    /// there is no digit.
+    pub fn calibration_value(&self, line: &str) -> u32 {
+        match (self.first_word(line), self.last_word(line)) {
+            (Some(first), Some(last)) => calibration_value(self.digits(&first), self.digits(&last)),
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vocabularies() {
+        let value = |vocabulary: &Vocabulary, line: &str| {
+            let value = vocabulary.scanner().calibration_value(line);
+            assert_eq!(vocabulary.naive_calibration_value(line), value);
+            value
+        };
+
+        let line = "xtwone3four";
+        assert_eq!(value(&Vocabulary::digits(), line), 33);
+        assert_eq!(value(&Vocabulary::english(), line), 24);
+        assert_eq!(value(&Vocabulary::english(), "eightwo"), 82);
+        assert_eq!(value(&Vocabulary::english(), "nothing"), 0);
+        assert_eq!(value(&Vocabulary::digits(), "a5b"), 55);
+
+        let french = Vocabulary::digits().with_spelled(Locale::French, 0..=9);
+        assert_eq!(value(&french, "zérotroisquatre"), 4);
+        assert_eq!(value(&french, "huitxsept"), 87);
+
+        let german = Vocabulary::new().with_spelled(Locale::German, 1..=9);
+        assert_eq!(value(&german, "fünf2zwei"), 52);
+        let german = german.with_spelled(Locale::German, 10..=20);
+        assert_eq!(value(&german, "xdreizehnx"), 13);
+    }
+
+    #[test]
+    fn test_multi_digit_words() {
+        let scanner = Vocabulary::digits()
+            .with_spelled(Locale::English, 0..=20)
+            .scanner();
+        // "sixteen" wins over "six", then "nine" overlaps it
+        let first = scanner.first_word("sixteenine").unwrap();
+        assert_eq!(
+            (first.start, first.end, scanner.digits(&first)),
+            (0, 7, "16")
+        );
+        let last = scanner.last_word("sixteenine").unwrap();
+        assert_eq!((last.start, last.end, scanner.digits(&last)), (6, 10, "9"));
+        assert_eq!(scanner.calibration_value("sixteenine"), 19);
+        assert_eq!(scanner.calibration_value("twelve"), 12);
+        assert_eq!(scanner.calibration_value("xtenx"), 10);
+
+        let custom = Vocabulary::new().with_word("dozen", 12).with_word("one", 1);
+        assert_eq!(custom.scanner().calibration_value("onedozen"), 12);
+        let custom = custom.with_word("dozen", 7);
+        assert_eq!(custom.scanner().calibration_value("dozen"), 77);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let vocabulary = Vocabulary::digits()
+            .with_spelled(Locale::English, 0..=20)
+            .with_spelled(Locale::German, 0..=20)
+            .with_word("é", 5);
+        let scanner = vocabulary.scanner();
+        for line in include_str!("input.txt").lines() {
+            let line = line.replace('t', "é");
+            assert_eq!(
+                scanner.calibration_value(&line),
+                vocabulary.naive_calibration_value(&line),
+                "{line}"
+            );
+        }
+    }
+}
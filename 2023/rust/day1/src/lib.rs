@@ -0,0 +1,38 @@
+use calibration::Vocabulary;
+use common::{colour, ParseError, Solution, SolveError};
+use report::Report;
+
+pub mod automaton;
+pub mod calibration;
+pub mod report;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Parsed = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn solve_part1(lines: &Self::Parsed) -> Result<Self::Answer1, SolveError> {
+        Ok(Vocabulary::digits().calibration_sum(lines))
+    }
+
+    fn solve_part2(lines: &Self::Parsed) -> Result<Self::Answer2, SolveError> {
+        Ok(Vocabulary::english().calibration_sum(lines))
+    }
+
+    fn explain(lines: &Self::Parsed, part: u8) -> Option<String> {
+        let vocabulary = match part {
+            1 => Vocabulary::digits(),
+            _ => Vocabulary::english(),
+        };
+        let report = Report::new(lines, &vocabulary).with_colour(colour::enabled());
+        Some(report.to_string())
+    }
+}
@@ -0,0 +1,251 @@
+use std::{fmt, ops::Range};
+
+use common::colour::{self, Colour};
+
+use crate::{automaton::Match, calibration::Vocabulary};
+
+/// A word found in a line, standing for some digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The bytes of the line the word spans.
+    pub span: Range<usize>,
+    pub word: String,
+    pub digits: String,
+}
+
+/// How the calibration value of one line was found.
+#[derive(Debug, Clone)]
+pub struct LineReport {
+    pub line: String,
+    /// All the words of the line, even overlapping ones, by start.
+    pub tokens: Vec<Token>,
+    /// The index of the token giving the first digit, if any.
+    pub first: Option<usize>,
+    /// The index of the token giving the last digit, if any.
+    pub last: Option<usize>,
+    pub value: u32,
+}
+
+impl LineReport {
+    /// How the token at `span` is used: whether it gives the first digit, the
+    /// last one, both, as a lone digit does, or neither.
+    fn roles(&self, span: &Range<usize>) -> Vec<(&'static str, Colour)> {
+        let is = |token: Option<usize>| token.is_some_and(|t| self.tokens[t].span == *span);
+        let mut roles = vec![];
+        if is(self.first) {
+            roles.push(("first", Colour::Green));
+        }
+        if is(self.last) {
+            roles.push(("last", Colour::Cyan));
+        }
+        roles
+    }
+
+    /// The colour of the character at byte `i`: that of the first digit before
+    /// that of the last one, and that of any other token last.
+    fn colour_at(&self, i: usize) -> Option<Colour> {
+        let covers = |t: Option<usize>| t.is_some_and(|t| self.tokens[t].span.contains(&i));
+        if covers(self.first) {
+            Some(Colour::Green)
+        } else if covers(self.last) {
+            Some(Colour::Cyan)
+        } else if self.tokens.iter().any(|token| token.span.contains(&i)) {
+            Some(Colour::Yellow)
+        } else {
+            None
+        }
+    }
+
+    /// The line with its tokens coloured.
+    fn painted_line(&self) -> String {
+        let mut painted = String::new();
+        let mut run = String::new();
+        let mut run_colour: Option<Colour> = None;
+        let mut flush = |run: &mut String, colour: Option<Colour>| {
+            match colour {
+                Some(colour) => painted.push_str(&colour::paint(run, colour)),
+                None => painted.push_str(run),
+            }
+            run.clear();
+        };
+
+        for (i, c) in self.line.char_indices() {
+            let colour = self.colour_at(i);
+            if colour != run_colour {
+                flush(&mut run, run_colour);
+                run_colour = colour;
+            }
+            run.push(c);
+        }
+        flush(&mut run, run_colour);
+
+        painted
+    }
+
+    /// Carets under the first digit, tildes under the last one, and plus signs
+    /// where they overlap, as when one token gives both, for when there are no
+    /// colours.
+    fn markers(&self) -> String {
+        let covers =
+            |t: Option<usize>, i: usize| t.is_some_and(|t| self.tokens[t].span.contains(&i));
+        let markers: String = self
+            .line
+            .char_indices()
+            .map(
+                |(i, _)| match (covers(self.first, i), covers(self.last, i)) {
+                    (true, true) => '+',
+                    (true, false) => '^',
+                    (false, true) => '~',
+                    (false, false) => ' ',
+                },
+            )
+            .collect();
+        markers.trim_end().to_string()
+    }
+}
+
+/// How the calibration value of every line was found.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub lines: Vec<LineReport>,
+    pub total: u32,
+    /// Whether to colour the tokens for a terminal, or mark them in plain text.
+    colour: bool,
+}
+
+impl Report {
+    pub fn new<S: AsRef<str>>(lines: &[S], vocabulary: &Vocabulary) -> Report {
+        let scanner = vocabulary.scanner();
+        let lines: Vec<LineReport> = lines
+            .iter()
+            .map(|line| {
+                let line = line.as_ref();
+                let tokens: Vec<Token> = scanner
+                    .words(line)
+                    .iter()
+                    .map(|m| Token {
+                        span: m.start..m.end,
+                        word: line[m.start..m.end].to_string(),
+                        digits: scanner.digits(m).to_string(),
+                    })
+                    .collect();
+                let index = |m: Option<Match>| {
+                    m.and_then(|m| {
+                        tokens
+                            .iter()
+                            .position(|token| token.span == (m.start..m.end))
+                    })
+                };
+
+                LineReport {
+                    line: line.to_string(),
+                    first: index(scanner.first_word(line)),
+                    last: index(scanner.last_word(line)),
+                    value: scanner.calibration_value(line),
+                    tokens,
+                }
+            })
+            .collect();
+
+        Report {
+            total: lines.iter().map(|line| line.value).sum(),
+            lines,
+            colour: false,
+        }
+    }
+
+    pub fn with_colour(mut self, colour: bool) -> Report {
+        self.colour = colour;
+        self
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, line) in self.lines.iter().enumerate() {
+            let prefix = format!("Line {}: ", i + 1);
+            if self.colour {
+                writeln!(f, "{prefix}{}", line.painted_line())?;
+            } else {
+                writeln!(f, "{prefix}{}", line.line)?;
+                let markers = line.markers();
+                if !markers.is_empty() {
+                    writeln!(f, "{}{markers}", " ".repeat(prefix.len()))?;
+                }
+            }
+
+            if line.tokens.is_empty() {
+                writeln!(f, "  no digits")?;
+            }
+            for token in &line.tokens {
+                let span = format!("{}..{}", token.span.start, token.span.end);
+                write!(f, "  {span:<9} {:<10} {:>3}", token.word, token.digits)?;
+                let roles: Vec<String> = line
+                    .roles(&token.span)
+                    .into_iter()
+                    .map(|(role, colour)| {
+                        if self.colour {
+                            colour::paint(role, colour)
+                        } else {
+                            role.to_string()
+                        }
+                    })
+                    .collect();
+                if roles.is_empty() {
+                    writeln!(f)?;
+                } else {
+                    writeln!(f, "  {}", roles.join(", "))?;
+                }
+            }
+            writeln!(f, "  = {}", line.value)?;
+        }
+
+        write!(f, "Total: {}", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+    use crate::Day1;
+
+    #[test]
+    fn test_report() {
+        let lines = Day1::parse("eightwo3\nnone\n5").unwrap();
+        let report = Report::new(&lines, &Vocabulary::english());
+
+        assert_eq!(report.total, 83 + 11 + 55);
+        let eightwo = &report.lines[0];
+        let words: Vec<&str> = eightwo.tokens.iter().map(|t| t.word.as_str()).collect();
+        assert_eq!(words, ["eight", "two", "3"]);
+        assert_eq!((eightwo.first, eightwo.last), (Some(0), Some(2)));
+        assert_eq!(report.lines[1].tokens[0].span, 1..4);
+        assert_eq!(report.lines[1].first, report.lines[1].last);
+
+        assert_eq!(
+            report.to_string(),
+            "Line 1: eightwo3
+        ^^^^^  ~
+  0..5      eight        8  first
+  4..7      two          2
+  7..8      3            3  last
+  = 83
+Line 2: none
+         +++
+  1..4      one          1  first, last
+  = 11
+Line 3: 5
+        +
+  0..1      5            5  first, last
+  = 55
+Total: 149"
+        );
+
+        let coloured = report.with_colour(true).to_string();
+        assert!(
+            coloured.starts_with("Line 1: \x1b[32meight\x1b[0m\x1b[33mwo\x1b[0m\x1b[36m3\x1b[0m\n")
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use std::{fs, process::ExitCode};
+
+use common::{input, Solution};
+
+use crate::{min_bags_power_sum, parse_bag, possible_ids_sum, power_overflow, Bag, Day2};
+
+const USAGE: &str = "Usage: day2-partN [INPUT] [--bag SPEC | --bag-file PATH]
+
+SPEC lists the cubes in the bag, like \"12 red, 13 green, 14 blue\", with any
+colours. The file at PATH holds the same, on one line or several. Without
+either, the bag of the puzzle is used.";
+
+#[derive(Debug, Default)]
+struct Args {
+    input: Option<String>,
+    bag: Option<String>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bag" | "--bag-file" if parsed.bag.is_some() => {
+                return Err("The bag can only be given once.".into());
+            }
+            "--bag" => {
+                let spec = args
+                    .next()
+                    .ok_or_else(|| format!("Expected the cubes in the bag after '{arg}'."))?;
+                parsed.bag = Some(spec);
+            }
+            "--bag-file" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| format!("Expected a path after '{arg}'."))?;
+                let spec = fs::read_to_string(&path)
+                    .map_err(|e| format!("Could not read '{path}': {e}"))?;
+                parsed.bag = Some(spec);
+            }
+            _ if parsed.input.is_none() => parsed.input = Some(arg),
+            _ => return Err(format!("Unexpected argument '{arg}'.")),
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Entry point of the day 2 binaries, which take the bag from the command
+/// line.
+pub fn run_part(part: u8) -> ExitCode {
+    common::run_with(USAGE, parse_args, |args| answer(args, part))
+}
+
+fn answer(args: &Args, part: u8) -> Result<String, String> {
+    let bag = match &args.bag {
+        Some(spec) => parse_bag(spec).map_err(|e| format!("Invalid bag: {e}"))?,
+        None => Bag::puzzle_bag(),
+    };
+    let input = input::read(Day2::DAY, args.input.as_deref()).map_err(|e| e.to_string())?;
+    let games = Day2::parse(&input).map_err(|e| e.to_string())?;
+
+    Ok(match part {
+        1 => possible_ids_sum(&games, &bag).to_string(),
+        _ => min_bags_power_sum(&games, &bag)
+            .map_err(power_overflow)?
+            .to_string(),
+    })
+}
@@ -0,0 +1,370 @@
+use std::collections::BTreeMap;
+
+use common::{ParseError, Solution, SolveError};
+
+pub mod cli;
+
+/// Some cubes, counted by colour.
+///
+/// Any word is a colour, so a colour missing from the counts is one there is
+/// no cube of.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cubes {
+    counts: BTreeMap<String, u32>,
+}
+
+/// The cubes shown at once during a game.
+pub type Round = Cubes;
+
+/// The cubes a game is played with.
+pub type Bag = Cubes;
+
+impl Cubes {
+    /// Counts given several times for a colour add up.
+    pub fn new<'a>(counts: impl IntoIterator<Item = (&'a str, u32)>) -> Cubes {
+        let mut cubes = Cubes::default();
+        for (colour, count) in counts {
+            cubes.add(colour, count);
+        }
+        cubes
+    }
+
+    /// The bag of the puzzle.
+    pub fn puzzle_bag() -> Bag {
+        Cubes::new([("red", 12), ("green", 13), ("blue", 14)])
+    }
+
+    pub fn add(&mut self, colour: &str, count: u32) {
+        *self.counts.entry(colour.to_string()).or_default() += count;
+    }
+
+    pub fn count(&self, colour: &str) -> u32 {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    /// The colours counted, in alphabetical order.
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.counts.keys().map(String::as_str)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /// Whether these cubes can all be taken out of `bag` at once.
+    pub fn fits_in(&self, bag: &Bag) -> bool {
+        self.counts
+            .iter()
+            .all(|(colour, &count)| count <= bag.count(colour))
+    }
+
+    /// The most cubes of each colour in either.
+    pub fn max(&self, other: &Cubes) -> Cubes {
+        let mut max = self.clone();
+        for (colour, &count) in &other.counts {
+            let max_count = max.counts.entry(colour.clone()).or_default();
+            *max_count = (*max_count).max(count);
+        }
+        max
+    }
+
+    /// The numbers of cubes of each of `colours` multiplied together, or
+    /// `None` if that does not fit in a u64.
+    pub fn power<'a>(&self, colours: impl IntoIterator<Item = &'a str>) -> Option<u64> {
+        let counts: Vec<u64> = colours
+            .into_iter()
+            .map(|colour| self.count(colour) as u64)
+            .collect();
+        // A missing colour makes the power 0, however big the other counts
+        if counts.contains(&0) {
+            return Some(0);
+        }
+        counts
+            .into_iter()
+            .try_fold(1u64, |power, count| power.checked_mul(count))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+impl Game {
+    /// Whether the game could have been played with `bag`.
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.first_impossible_round(bag).is_none()
+    }
+
+    /// The index of the first round showing more cubes than `bag` has.
+    pub fn first_impossible_round(&self, bag: &Bag) -> Option<usize> {
+        self.rounds.iter().position(|round| !round.fits_in(bag))
+    }
+
+    /// The fewest cubes of each colour the game could have been played with,
+    /// which are the most shown at once.
+    pub fn min_bag(&self) -> Bag {
+        self.rounds
+            .iter()
+            .fold(Bag::default(), |bag, round| bag.max(round))
+    }
+
+    /// The number of cubes shown in each round.
+    pub fn round_totals(&self) -> impl Iterator<Item = u32> + '_ {
+        self.rounds.iter().map(Cubes::total)
+    }
+}
+
+/// Splits `s`, starting at byte `offset` of its line, on `sep`, along with
+/// the offset of each part.
+fn split_with_offsets(s: &str, offset: usize, sep: char) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = offset;
+    s.split(sep).map(move |part| {
+        let part_start = start;
+        start += part.len() + sep.len_utf8();
+        (part_start, part)
+    })
+}
+
+/// Parses some cubes of one colour, like "3 blue", at byte `offset` of `line`,
+/// adding them to `cubes`.
+fn parse_cubes(
+    line_idx: usize,
+    line: &str,
+    offset: usize,
+    text: &str,
+    cubes: &mut Cubes,
+) -> Result<(), ParseError> {
+    let start = offset + text.len() - text.trim_start().len();
+    let text = text.trim();
+    let end = start + text.len();
+
+    let Some((count, colour)) = text.split_once(' ') else {
+        if text.is_empty() {
+            return Err(ParseError::new(
+                Day2::DAY,
+                line_idx,
+                line,
+                start..end,
+                "a number of cubes and a colour, like '3 blue'",
+            ));
+        }
+        return Err(ParseError::new(
+            Day2::DAY,
+            line_idx,
+            line,
+            end..end,
+            "a colour after the number of cubes",
+        ));
+    };
+    let count: u32 = ParseError::number(Day2::DAY, line_idx, line, start..start + count.len())?;
+
+    let colour_start = end - colour.len();
+    if colour.is_empty() || !colour.chars().all(|c| c.is_alphabetic()) {
+        return Err(ParseError::new(
+            Day2::DAY,
+            line_idx,
+            line,
+            colour_start..end,
+            "a colour, like 'blue'",
+        ));
+    }
+    cubes.add(colour, count);
+
+    Ok(())
+}
+
+fn parse_game(line_idx: usize, line: &str) -> Result<Game, ParseError> {
+    let invalid_game = || {
+        ParseError::line(
+            Day2::DAY,
+            line_idx,
+            line,
+            "a game, like 'Game 1: 3 blue, 4 red; 1 green'",
+        )
+    };
+    let (header, rounds_text) = line.split_once(':').ok_or_else(invalid_game)?;
+    let id = header.strip_prefix("Game ").ok_or_else(invalid_game)?;
+    let id_start = header.len() - id.len();
+    let id: u32 = ParseError::number(Day2::DAY, line_idx, line, id_start..header.len())?;
+
+    let rounds = split_with_offsets(rounds_text, header.len() + 1, ';')
+        .map(|(offset, round_text)| {
+            let mut round = Round::default();
+            for (offset, text) in split_with_offsets(round_text, offset, ',') {
+                parse_cubes(line_idx, line, offset, text, &mut round)?;
+            }
+            Ok(round)
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Game { id, rounds })
+}
+
+/// Parses a bag, like "12 red, 13 green, 14 blue", with as many colours as
+/// wanted, on one line or several.
+pub fn parse_bag(spec: &str) -> Result<Bag, ParseError> {
+    let mut bag = Bag::default();
+    for (line_idx, line) in spec.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        for (offset, text) in split_with_offsets(line, 0, ',') {
+            parse_cubes(line_idx, line, offset, text, &mut bag)?;
+        }
+    }
+
+    Ok(bag)
+}
+
+/// The sum of the ids of the games that could have been played with `bag`.
+pub fn possible_ids_sum(games: &[Game], bag: &Bag) -> u64 {
+    games
+        .iter()
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id as u64)
+        .sum()
+}
+
+/// The sum of the powers of the smallest bags of the games, over the colours
+/// of `bag`, or the id of the game making it overflow a u64.
+pub fn min_bags_power_sum(games: &[Game], bag: &Bag) -> Result<u64, u32> {
+    games.iter().try_fold(0u64, |sum, game| {
+        game.min_bag()
+            .power(bag.colours())
+            .and_then(|power| sum.checked_add(power))
+            .ok_or(game.id)
+    })
+}
+
+/// Why there is no sum of powers.
+fn power_overflow(id: u32) -> String {
+    format!("The powers of the smallest bags overflow a u64 at game {id}.")
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Parsed = Vec<Game>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(line_idx, line)| parse_game(line_idx, line))
+            .collect()
+    }
+
+    fn solve_part1(games: &Self::Parsed) -> Result<Self::Answer1, SolveError> {
+        Ok(possible_ids_sum(games, &Bag::puzzle_bag()))
+    }
+
+    fn solve_part2(games: &Self::Parsed) -> Result<Self::Answer2, SolveError> {
+        min_bags_power_sum(games, &Bag::puzzle_bag())
+            .map_err(|id| SolveError::new(Self::DAY, 2, power_overflow(id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game() {
+        let game = parse_game(
+            0,
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        )
+        .unwrap();
+        assert_eq!(game.id, 3);
+        assert_eq!(
+            game.rounds,
+            [
+                Round::new([("red", 20), ("green", 8), ("blue", 6)]),
+                Round::new([("red", 4), ("green", 13), ("blue", 5)]),
+                Round::new([("red", 1), ("green", 5)])
+            ]
+        );
+        assert_eq!(game.round_totals().collect::<Vec<_>>(), [34, 22, 6]);
+        assert_eq!(
+            game.min_bag(),
+            Bag::new([("red", 20), ("green", 13), ("blue", 6)])
+        );
+
+        assert_eq!(game.first_impossible_round(&Bag::puzzle_bag()), Some(0));
+        let bag = Bag::new([("red", 20), ("green", 12), ("blue", 6)]);
+        assert_eq!(game.first_impossible_round(&bag), Some(1));
+        assert!(game.is_possible(&game.min_bag()));
+    }
+
+    #[test]
+    fn test_colours() {
+        let games = Day2::parse("Game 1: 2 teal, 1 red; 3 teal\nGame 2: 1 ochre, 2 red").unwrap();
+        assert_eq!(Day2::solve_part1(&games), Ok(0));
+        assert_eq!(Day2::solve_part2(&games), Ok(0));
+
+        let bag = parse_bag("3 teal, 2 red\n\n1 ochre\n1 ochre").unwrap();
+        assert_eq!(bag.colours().collect::<Vec<_>>(), ["ochre", "red", "teal"]);
+        assert_eq!(bag.count("ochre"), 2);
+        assert_eq!(possible_ids_sum(&games, &bag), 3);
+        // 0 * 1 * 3 + 1 * 2 * 0
+        assert_eq!(min_bags_power_sum(&games, &bag), Ok(0));
+        let bag = parse_bag("3 teal, 2 red").unwrap();
+        assert_eq!(possible_ids_sum(&games, &bag), 1);
+        assert_eq!(min_bags_power_sum(&games, &bag), Ok(3));
+        assert_eq!(
+            min_bags_power_sum(&games, &parse_bag("9 red").unwrap()),
+            Ok(3)
+        );
+    }
+
+    #[test]
+    fn test_power_overflow() {
+        let max = u32::MAX;
+        let games = Day2::parse(&format!(
+            "Game 1: 2 red\nGame 7: {max} red, {max} blue; {max} green"
+        ))
+        .unwrap();
+        let bag = parse_bag("1 red, 1 blue, 1 green").unwrap();
+        assert_eq!(games[1].min_bag().power(bag.colours()), None);
+        assert_eq!(min_bags_power_sum(&games, &bag), Err(7));
+        assert_eq!(
+            min_bags_power_sum(&games, &parse_bag("1 red, 1 blue").unwrap()),
+            Ok((max as u64).pow(2))
+        );
+        assert_eq!(
+            min_bags_power_sum(
+                &games,
+                &parse_bag("1 red, 1 blue, 1 green, 1 pink").unwrap()
+            ),
+            Ok(0)
+        );
+        assert!(Day2::solve_part2(&games).is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = parse_game(2, "Game 3: 8 green, 6 pur-ple").unwrap_err();
+        assert_eq!((err.column, err.snippet.as_str()), (20, "pur-ple"));
+
+        let err = parse_game(0, "Game 1: 3 blue, 4").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (18, "a colour after the number of cubes")
+        );
+
+        let err = parse_game(0, "Game 1: 3 blue;; 1 red").unwrap_err();
+        assert_eq!(err.column, 16);
+
+        let err = parse_game(0, "Game x: 3 blue").unwrap_err();
+        assert_eq!(err.snippet, "x");
+
+        assert!(parse_game(0, "3 blue, 4 red").is_err());
+        assert_eq!(parse_bag("12 red\n3").unwrap_err().line, 2);
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use common::grid::{Grid, Pos};
+
+use crate::Number;
+
+/// A symbol of the schematic, with the numbers around it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub pos: Pos,
+    pub c: char,
+    /// Indices of the numbers around the symbol, in reading order.
+    pub numbers: Vec<usize>,
+}
+
+impl Symbol {
+    /// The number of numbers around the symbol.
+    pub fn degree(&self) -> usize {
+        self.numbers.len()
+    }
+}
+
+/// Which numbers are next to which symbols, both ways.
+///
+/// Numbers are referred to by their index in the list the graph was built
+/// from, and symbols by their position.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    /// All the symbols, in reading order, even those next to no number.
+    symbols: Vec<Symbol>,
+    symbol_indices: HashMap<Pos, usize>,
+    /// For each number, the indices of the symbols around it.
+    symbols_by_number: Vec<Vec<usize>>,
+}
+
+pub fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+impl Graph {
+    pub fn new(grid: &Grid<char>, numbers: &[Number]) -> Graph {
+        let mut symbols: Vec<Symbol> = vec![];
+        let mut symbol_indices: HashMap<Pos, usize> = HashMap::new();
+        for pos in grid.positions() {
+            let c = *grid.get(pos).unwrap();
+            if is_symbol(c) {
+                symbol_indices.insert(pos, symbols.len());
+                symbols.push(Symbol {
+                    pos,
+                    c,
+                    numbers: vec![],
+                });
+            }
+        }
+
+        let mut symbols_by_number: Vec<Vec<usize>> = vec![];
+        for (n, number) in numbers.iter().enumerate() {
+            let around: Vec<usize> = grid
+                .span_neighbours(number.row, number.cols.clone())
+                .filter_map(|pos| symbol_indices.get(&pos).copied())
+                .collect();
+            for &s in &around {
+                symbols[s].numbers.push(n);
+            }
+            symbols_by_number.push(around);
+        }
+
+        Graph {
+            symbols,
+            symbol_indices,
+            symbols_by_number,
+        }
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn symbol_at(&self, pos: Pos) -> Option<&Symbol> {
+        self.symbol_indices.get(&pos).map(|&s| &self.symbols[s])
+    }
+
+    /// The symbols `c` next to exactly `degree` numbers, or any number of them
+    /// if `degree` is `None`.
+    pub fn symbols_with(&self, c: char, degree: Option<usize>) -> impl Iterator<Item = &Symbol> {
+        self.symbols
+            .iter()
+            .filter(move |symbol| symbol.c == c && degree.is_none_or(|d| symbol.degree() == d))
+    }
+
+    /// The indices of the numbers next to the symbol at `pos`, if there is one.
+    pub fn numbers_adjacent_to(&self, pos: Pos) -> &[usize] {
+        self.symbol_at(pos)
+            .map(|symbol| symbol.numbers.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The symbols next to the number of index `number`.
+    pub fn symbols_adjacent_to(&self, number: usize) -> impl Iterator<Item = &Symbol> {
+        self.symbols_by_number[number]
+            .iter()
+            .map(|&s| &self.symbols[s])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use crate::Day3;
+
+    #[test]
+    fn test_graph() {
+        let schematic = Day3::parse("467..114..\n...*......\n..35..633.\n#.....#...").unwrap();
+        let graph = schematic.graph();
+
+        assert_eq!(graph.symbols().len(), 3);
+        assert_eq!(graph.numbers_adjacent_to((1, 3)), [0, 2]);
+        assert!(graph.numbers_adjacent_to((0, 0)).is_empty());
+        assert_eq!(graph.numbers_adjacent_to((3, 6)), [3]);
+        assert!(graph.numbers_adjacent_to((3, 0)).is_empty());
+
+        let symbols: Vec<char> = graph.symbols_adjacent_to(3).map(|s| s.c).collect();
+        assert_eq!(symbols, ['#']);
+        assert_eq!(graph.symbols_adjacent_to(1).count(), 0);
+
+        assert_eq!(graph.symbols_with('*', Some(2)).count(), 1);
+        assert_eq!(graph.symbols_with('#', None).count(), 2);
+        assert_eq!(graph.symbols_with('#', Some(0)).count(), 1);
+    }
+}
@@ -0,0 +1,202 @@
+use std::ops::Range;
+
+use common::{grid::Grid, ParseError, Solution, SolveError};
+use graph::Graph;
+
+pub mod graph;
+
+/// A number of the schematic, spanning some columns of a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub cols: Range<usize>,
+}
+
+/// The engine schematic, with the numbers found in it.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    grid: Grid<char>,
+    numbers: Vec<Number>,
+    graph: Graph,
+}
+
+impl Schematic {
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// The numbers next to a symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(|&(n, _)| self.graph.symbols_adjacent_to(n).next().is_some())
+            .map(|(_, number)| number)
+    }
+
+    /// For each symbol `c` next to exactly `degree` numbers, the product of
+    /// these numbers.
+    pub fn ratios(&self, c: char, degree: usize) -> impl Iterator<Item = u64> + '_ {
+        self.graph.symbols_with(c, Some(degree)).map(|symbol| {
+            symbol
+                .numbers
+                .iter()
+                .map(|&n| self.numbers[n].value as u64)
+                .product()
+        })
+    }
+}
+
+/// The lines of `input`, without the `'\r'` a last line may end with.
+///
+/// `str::lines` already drops the `'\r'` of `"\r\n"`, but not that of a last
+/// line with no `'\n'`.
+fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Parsed = Schematic;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut numbers: Vec<Number> = vec![];
+
+        for (i, line) in lines(input).enumerate() {
+            if let Some((col, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_graphic()) {
+                return Err(ParseError::new(
+                    Self::DAY,
+                    i,
+                    line,
+                    col..col + c.len_utf8(),
+                    "a digit, '.' or a symbol",
+                ));
+            }
+
+            // Check that the numbers are not too big
+            let bytes = line.as_bytes();
+            let mut col = 0;
+            while col < bytes.len() {
+                if bytes[col].is_ascii_digit() {
+                    let start = col;
+                    while col < bytes.len() && bytes[col].is_ascii_digit() {
+                        col += 1;
+                    }
+                    // Only ASCII, so columns are bytes
+                    let value = ParseError::number(Self::DAY, i, line, start..col)?;
+                    numbers.push(Number {
+                        value,
+                        row: i,
+                        cols: start..col,
+                    });
+                } else {
+                    col += 1;
+                }
+            }
+        }
+
+        // Short lines are padded with '.', which is nothing
+        let grid = Grid::from_rows(lines(input).map(str::chars), '.');
+        let graph = Graph::new(&grid, &numbers);
+        Ok(Schematic {
+            grid,
+            numbers,
+            graph,
+        })
+    }
+
+    fn solve_part1(schematic: &Self::Parsed) -> Result<Self::Answer1, SolveError> {
+        Ok(schematic
+            .part_numbers()
+            .map(|number| number.value as u64)
+            .sum())
+    }
+
+    fn solve_part2(schematic: &Self::Parsed) -> Result<Self::Answer2, SolveError> {
+        // Gears are stars next to exactly two numbers
+        Ok(schematic.ratios('*', 2).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(input: &str) -> (u64, u64) {
+        let schematic = Day3::parse(input).unwrap();
+        (
+            Day3::solve_part1(&schematic).unwrap(),
+            Day3::solve_part2(&schematic).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_edges() {
+        // Numbers touching the left and right edges
+        assert_eq!(answers("12*\n..."), (12, 0));
+        assert_eq!(answers("*12\n..."), (12, 0));
+        assert_eq!(answers("123\n*.."), (123, 0));
+        assert_eq!(answers("123\n..*"), (123, 0));
+        assert_eq!(answers("123\n..."), (0, 0));
+        assert_eq!(answers("..\n.7\n*."), (7, 0));
+
+        // A number starting a line right after one ending the line above
+        let schematic = Day3::parse("..12\n34.*").unwrap();
+        assert_eq!(schematic.numbers()[1].cols, 0..2);
+        assert_eq!(Day3::solve_part1(&schematic), Ok(12));
+    }
+
+    #[test]
+    fn test_single_column() {
+        assert_eq!(answers("1\n*\n2"), (3, 2));
+        assert_eq!(answers("1\n.\n*\n.\n2"), (0, 0));
+        assert_eq!(answers("*\n1\n2"), (1, 0));
+    }
+
+    #[test]
+    fn test_big_numbers() {
+        let n = 4_000_000_000u64;
+        assert_eq!(answers("4000000000*4000000000"), (2 * n, n * n));
+    }
+
+    #[test]
+    fn test_ragged() {
+        // The short lines are padded with '.'
+        assert_eq!(answers("1\n.*"), (1, 0));
+        assert_eq!(answers("....5\n*"), (0, 0));
+        assert_eq!(answers("*\n\n..\n....5\n...#"), (5, 0));
+        assert_eq!(answers("3*\n\n4"), (3, 0));
+    }
+
+    #[test]
+    fn test_crlf() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n\
+            .....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+        assert_eq!(answers(input), (4361, 467835));
+        assert_eq!(answers(&input.replace('\n', "\r\n")), (4361, 467835));
+        assert_eq!(
+            answers(&(input.replace('\n', "\r\n") + "\r")),
+            (4361, 467835)
+        );
+        assert_eq!(answers("12*\r"), (12, 0));
+
+        // Only at the end of lines
+        assert!(Day3::parse("1\r2*").is_err());
+    }
+}
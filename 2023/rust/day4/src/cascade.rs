@@ -0,0 +1,175 @@
+use std::fmt;
+
+use crate::{Counted, OverflowError, Scratchcard};
+
+/// Copies of a card won by the copies of an earlier one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Won {
+    /// The id of the earlier card.
+    pub from: u32,
+    pub copies: u64,
+}
+
+/// The copies of each card held once all of them have been scratched, with
+/// one of each card to begin with.
+///
+/// A card wins copies of the cards following it in the pile, as many as it
+/// has matches, but never of cards past the last one.
+#[derive(Debug, Clone)]
+pub struct Cascade {
+    ids: Vec<u32>,
+    /// The copies held of each card, in the order of the pile, the original
+    /// included.
+    counts: Vec<u64>,
+    /// For each card, the copies won from each earlier card, when traced.
+    trace: Option<Vec<Vec<Won>>>,
+    total: u64,
+}
+
+impl Cascade {
+    pub fn new(cards: &[Scratchcard]) -> Result<Cascade, OverflowError> {
+        Cascade::run(cards, false)
+    }
+
+    /// The same as [`Cascade::new`], also keeping where the copies of each
+    /// card come from.
+    pub fn traced(cards: &[Scratchcard]) -> Result<Cascade, OverflowError> {
+        Cascade::run(cards, true)
+    }
+
+    fn run(cards: &[Scratchcard], trace: bool) -> Result<Cascade, OverflowError> {
+        let n = cards.len();
+        let mut counts: Vec<u64> = vec![1; n];
+        let mut won: Option<Vec<Vec<Won>>> = trace.then(|| vec![vec![]; n]);
+
+        for (i, card) in cards.iter().enumerate() {
+            let copies = counts[i];
+            let end = (i + 1 + card.matches() as usize).min(n);
+            for j in i + 1..end {
+                counts[j] = counts[j].checked_add(copies).ok_or(OverflowError {
+                    card: cards[j].id,
+                    counted: Counted::Copies,
+                })?;
+                if let Some(won) = &mut won {
+                    won[j].push(Won {
+                        from: card.id,
+                        copies,
+                    });
+                }
+            }
+        }
+
+        let total = counts
+            .iter()
+            .zip(cards)
+            .try_fold(0u64, |total, (&count, card)| {
+                total.checked_add(count).ok_or(OverflowError {
+                    card: card.id,
+                    counted: Counted::Copies,
+                })
+            })?;
+
+        Ok(Cascade {
+            ids: cards.iter().map(|card| card.id).collect(),
+            counts,
+            trace: won,
+            total,
+        })
+    }
+
+    /// The copies held of each card, in the order of the pile.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// The copies the card at `index` in the pile won from each earlier card,
+    /// if the cascade was traced.
+    pub fn won(&self, index: usize) -> Option<&[Won]> {
+        self.trace.as_ref().map(|trace| trace[index].as_slice())
+    }
+
+    /// The number of cards held in the end.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+impl fmt::Display for Cascade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (id, count)) in self.ids.iter().zip(&self.counts).enumerate() {
+            let plural = if *count == 1 { "y" } else { "ies" };
+            write!(f, "Card {id:>4}: {count:>10} cop{plural}")?;
+            if let Some(won) = self.won(i).filter(|won| !won.is_empty()) {
+                write!(f, ", 1 original")?;
+                for won in won {
+                    write!(f, " + {} from card {}", won.copies, won.from)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        write!(f, "Total: {}", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+    use crate::Day4;
+
+    #[test]
+    fn test_cascade() {
+        let cards = Day4::parse(include_str!("example.txt")).unwrap();
+        let cascade = Cascade::traced(&cards).unwrap();
+        assert_eq!(cascade.counts(), [1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.total(), 30);
+        assert_eq!(
+            cascade.won(3).unwrap(),
+            [
+                Won { from: 1, copies: 1 },
+                Won { from: 2, copies: 2 },
+                Won { from: 3, copies: 4 }
+            ]
+        );
+        assert_eq!(
+            cascade.to_string().lines().nth(3),
+            Some("Card    4:          8 copies, 1 original + 1 from card 1 + 2 from card 2 + 4 from card 3")
+        );
+        assert!(Cascade::new(&cards).unwrap().won(3).is_none());
+
+        // The last card wins nothing, though it has matches
+        let cards = Day4::parse("Card 1: 1 2 | 1 2").unwrap();
+        assert_eq!(Cascade::new(&cards).unwrap().counts(), [1]);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Each card wins a copy of all the following ones, doubling their copies
+        let pile = |n: u32| -> Vec<Scratchcard> {
+            (1..=n)
+                .map(|id| Scratchcard {
+                    id,
+                    winning: (1..=n).collect(),
+                    owned: (1..=n).collect(),
+                })
+                .collect()
+        };
+
+        let cascade = Cascade::new(&pile(64)).unwrap();
+        assert_eq!(cascade.counts()[63], 1 << 63);
+        assert_eq!(cascade.total(), u64::MAX);
+        assert_eq!(
+            Cascade::new(&pile(65)).unwrap_err(),
+            OverflowError {
+                card: 65,
+                counted: Counted::Copies
+            }
+        );
+        assert_eq!(
+            Day4::solve_part2(&pile(65)).unwrap_err().to_string(),
+            "No answer for day 4 part 2: Card 65 ends up with more copies than fit in a u64."
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::{collections::HashSet, error, fmt};
+
+use common::{ParseError, Solution, SolveError};
+use regex::Regex;
+
+use cascade::Cascade;
+
+pub mod cascade;
+
+/// A scratchcard, with the winning numbers kept in a set to check the numbers
+/// on the card against.
+#[derive(Debug, Clone)]
+pub struct Scratchcard {
+    pub id: u32,
+    pub winning: HashSet<u32>,
+    /// The numbers on the card, in order.
+    pub owned: Vec<u32>,
+}
+
+impl Scratchcard {
+    /// The number of numbers on the card that are winning numbers.
+    pub fn matches(&self) -> u32 {
+        self.owned
+            .iter()
+            .filter(|n| self.winning.contains(n))
+            .count() as u32
+    }
+
+    /// 1 point for the first match, doubled for each of the others.
+    pub fn points(&self) -> Result<u64, OverflowError> {
+        match self.matches() {
+            0 => Ok(0),
+            matches => 1u64.checked_shl(matches - 1).ok_or(OverflowError {
+                card: self.id,
+                counted: Counted::Points,
+            }),
+        }
+    }
+}
+
+/// What a card ends up with too much of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counted {
+    Points,
+    Copies,
+}
+
+/// A card ending up with more points or copies than can be counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverflowError {
+    /// The id of the card.
+    pub card: u32,
+    pub counted: Counted,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counted = match self.counted {
+            Counted::Points => "points",
+            Counted::Copies => "copies",
+        };
+        write!(
+            f,
+            "Card {} ends up with more {counted} than fit in a u64.",
+            self.card
+        )
+    }
+}
+
+impl error::Error for OverflowError {}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Parsed = Vec<Scratchcard>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let card_re =
+            Regex::new(r"^Card +(?<id>\d+): +(?<winning>(\d| )+) +\| +(?<owned>(\d| )+)$").unwrap();
+        let number_re = Regex::new(r"\d+").unwrap();
+
+        let mut cards: Vec<Scratchcard> = vec![];
+
+        for (i, line) in input.lines().enumerate() {
+            let captures = card_re.captures(line).ok_or_else(|| {
+                ParseError::line(
+                    Self::DAY,
+                    i,
+                    line,
+                    "a card, like 'Card 1: 41 48 83 | 83 86  6'",
+                )
+            })?;
+
+            // Parses all the numbers of a group of the line
+            let numbers = |group: &str| -> Result<Vec<u32>, ParseError> {
+                let group = captures.name(group).unwrap();
+                number_re
+                    .find_iter(group.as_str())
+                    .map(|n| {
+                        ParseError::number(
+                            Self::DAY,
+                            i,
+                            line,
+                            group.start() + n.start()..group.start() + n.end(),
+                        )
+                    })
+                    .collect()
+            };
+
+            let id = captures.name("id").unwrap();
+            let id: u32 = ParseError::number(Self::DAY, i, line, id.range())?;
+
+            cards.push(Scratchcard {
+                id,
+                winning: numbers("winning")?.into_iter().collect(),
+                owned: numbers("owned")?,
+            });
+        }
+
+        Ok(cards)
+    }
+
+    fn solve_part1(cards: &Self::Parsed) -> Result<Self::Answer1, SolveError> {
+        // The card to blame for an overflowing sum is the one pushing it over
+        cards
+            .iter()
+            .try_fold(0u64, |sum, card| {
+                sum.checked_add(card.points()?).ok_or(OverflowError {
+                    card: card.id,
+                    counted: Counted::Points,
+                })
+            })
+            .map_err(|e| SolveError::new(Self::DAY, 1, e.to_string()))
+    }
+
+    fn solve_part2(cards: &Self::Parsed) -> Result<Self::Answer2, SolveError> {
+        Cascade::new(cards)
+            .map(|cascade| cascade.total())
+            .map_err(|e| SolveError::new(Self::DAY, 2, e.to_string()))
+    }
+
+    fn explain(cards: &Self::Parsed, part: u8) -> Option<String> {
+        match part {
+            1 => None,
+            _ => Some(match Cascade::traced(cards) {
+                Ok(cascade) => cascade.to_string(),
+                Err(e) => e.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scratchcard() {
+        let cards =
+            Day4::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 1 | 2").unwrap();
+        let card = &cards[0];
+        assert_eq!(card.owned, [83, 86, 6, 31, 17, 9, 48, 53]);
+        assert_eq!(card.matches(), 4);
+        assert_eq!(card.points(), Ok(8));
+
+        assert_eq!(cards[1].points(), Ok(0));
+        assert_eq!(cards[1].matches(), 0);
+    }
+
+    #[test]
+    fn test_points_overflow() {
+        let card = |id: u32, matches: u32| {
+            let numbers: Vec<String> = (1..=matches).map(|n| n.to_string()).collect();
+            let numbers = numbers.join(" ");
+            format!("Card {id}: {numbers} | {numbers}")
+        };
+
+        let cards = Day4::parse(&card(1, 64)).unwrap();
+        assert_eq!(cards[0].points(), Ok(1 << 63));
+        assert_eq!(Day4::solve_part1(&cards), Ok(1 << 63));
+
+        let cards = Day4::parse(&card(1, 65)).unwrap();
+        assert_eq!(
+            Day4::solve_part1(&cards).unwrap_err().reason,
+            "Card 1 ends up with more points than fit in a u64."
+        );
+
+        let cards = Day4::parse(&format!("{}\n{}", card(1, 64), card(2, 64))).unwrap();
+        assert_eq!(
+            Day4::solve_part1(&cards).unwrap_err(),
+            SolveError::new(4, 1, "Card 2 ends up with more points than fit in a u64.")
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    iter::Sum,
+    ops::{Range, Sub},
+    slice,
+};
+
+/// A set of values, stored as sorted, disjoint and non-adjacent ranges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { ranges: vec![] }
+    }
+
+    /// Sorts `ranges`, drops the empty ones and merges the ones overlapping or
+    /// touching.
+    fn normalize(mut ranges: Vec<Range<T>>) -> IntervalSet<T> {
+        ranges.retain(|range| range.start < range.end);
+        ranges.sort_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<T>> = vec![];
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        IntervalSet { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, Range<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = IntervalSet::normalize(ranges);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.ranges.partition_point(|range| range.end <= value);
+        idx < self.ranges.len() && self.ranges[idx].start <= value
+    }
+
+    /// Whether all of `range` is in the set.
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.start >= range.end {
+            return true;
+        }
+        let idx = self.ranges.partition_point(|r| r.end <= range.start);
+        idx < self.ranges.len()
+            && self.ranges[idx].start <= range.start
+            && range.end <= self.ranges[idx].end
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        IntervalSet::normalize(self.iter().chain(other.iter()).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges: Vec<Range<T>> = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            // Move past the range ending first
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges: Vec<Range<T>> = vec![];
+        let mut j = 0;
+        for range in &self.ranges {
+            let mut start = range.start;
+            // Skip the ranges of `other` ending before this one
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                let cut = &other.ranges[k];
+                if start < cut.start {
+                    ranges.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+                k += 1;
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> IntervalSet<T> {
+    /// The number of values in the set.
+    pub fn total_len(&self) -> T {
+        self.ranges
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+}
+
+impl<T: Copy + Ord> Default for IntervalSet<T> {
+    fn default() -> IntervalSet<T> {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> IntervalSet<T> {
+        IntervalSet::normalize(vec![range])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> IntervalSet<T> {
+        IntervalSet::normalize(iter.into_iter().collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<u64>]) -> IntervalSet<u64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_normalize() {
+        let s = set(&[10..20, 5..5, 0..3, 15..25, 3..4, 30..31]);
+        assert_eq!(s.ranges(), [0..4, 10..25, 30..31]);
+        assert_eq!(s.total_len(), 20);
+        assert_eq!(s.min(), Some(0));
+        assert!(s.contains(3) && !s.contains(4) && s.contains(24) && !s.contains(25));
+        assert!(s.contains_range(&(12..25)) && !s.contains_range(&(3..11)));
+        assert!(set(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_algebra() {
+        let a = set(&[0..10, 20..30, 40..50]);
+        let b = set(&[5..25, 45..60]);
+        assert_eq!(a.union(&b).ranges(), [0..30, 40..60]);
+        assert_eq!(a.intersection(&b).ranges(), [5..10, 20..25, 45..50]);
+        assert_eq!(a.difference(&b).ranges(), [0..5, 25..30, 40..45]);
+        assert_eq!(b.difference(&a).ranges(), [10..20, 50..60]);
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(
+            IntervalSet::from(0..u64::MAX).difference(&b).ranges(),
+            [0..5, 25..45, 60..u64::MAX]
+        );
+    }
+}
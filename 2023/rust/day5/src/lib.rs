@@ -0,0 +1,235 @@
+use common::{ParseError, Solution, SolveError};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::ops::Range;
+
+use interval_set::IntervalSet;
+use map::{Map, RangeProduct};
+use map_chain::MapChain;
+
+pub mod interval_set;
+pub mod map;
+pub mod map_chain;
+
+// See https://stackoverflow.com/questions/35169259/how-to-make-a-compiled-regexp-a-global-variable
+lazy_static! {
+    static ref SEEDS_RE: Regex = Regex::new(r"^seeds: (?<seeds>(\d| )+)$").unwrap();
+    static ref NUM_RE: Regex = Regex::new(r"(?<n>\d+)").unwrap();
+}
+
+/// The seeds to be planted, with the maps to follow to find where to plant them.
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    chain: MapChain,
+    /// The whole chain from seed to location, as a single map.
+    seed_to_location: Map,
+}
+
+impl Almanac {
+    pub fn chain(&self) -> &MapChain {
+        &self.chain
+    }
+
+    /// The whole chain from seed to location, as a single map, e.g. to see how
+    /// many segments it is made of.
+    pub fn seed_to_location(&self) -> &Map {
+        &self.seed_to_location
+    }
+
+    /// The seeds read as pairs of start and length, as in part 2.
+    pub fn seed_ranges(&self) -> IntervalSet<u64> {
+        // Checked to pair up and not to overflow when parsing
+        let seed_ranges: IntervalSet<u64> = self
+            .seeds
+            .chunks_exact(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect();
+
+        seed_ranges
+    }
+
+    /// The lowest location of `seed_ranges`, found by going up the locations
+    /// until one is led to by a seed, instead of following every seed down to
+    /// its location.
+    pub fn lowest_location_upward(&self, seeds: &IntervalSet<u64>) -> Option<u64> {
+        let mut segments: Vec<&RangeProduct> = self.seed_to_location.ranges().iter().collect();
+        segments.sort_by_key(|rp| rp.dest.start);
+
+        let mut lowest: Option<u64> = None;
+        for rp in segments {
+            if lowest.is_some_and(|lowest| lowest <= rp.dest.start) {
+                // Segments overlap, but no later one can go lower
+                break;
+            }
+
+            let segment_seeds = seeds.intersection(&IntervalSet::from(rp.src.clone()));
+            if let Some(seed) = segment_seeds.min() {
+                let location = rp.dest.start + (seed - rp.src.start);
+                lowest = Some(lowest.map_or(location, |lowest| lowest.min(location)));
+            }
+        }
+
+        lowest
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Parsed = Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut lines = input.lines().enumerate();
+
+        // Find the seeds
+        let (_, first_line) = lines
+            .next()
+            .ok_or_else(|| ParseError::missing_line(Self::DAY, 0, "the seeds"))?;
+        let seeds_cap = SEEDS_RE.captures(first_line).ok_or_else(|| {
+            ParseError::line(Self::DAY, 0, first_line, "the seeds, like 'seeds: 79 14'")
+        })?;
+        let seeds_start = seeds_cap.name("seeds").unwrap().start();
+        let seed_spans: Vec<Range<usize>> = NUM_RE
+            .find_iter(&seeds_cap["seeds"])
+            .map(|n| seeds_start + n.start()..seeds_start + n.end())
+            .collect();
+        let seeds: Vec<u64> = seed_spans
+            .iter()
+            .map(|span| ParseError::number(Self::DAY, 0, first_line, span.clone()))
+            .collect::<Result<_, _>>()?;
+        if seeds.is_empty() {
+            let seeds = seeds_cap.name("seeds").unwrap();
+            return Err(ParseError::new(
+                Self::DAY,
+                0,
+                first_line,
+                seeds.range(),
+                "at least one seed",
+            ));
+        }
+
+        // Read as pairs of start and length, the seeds must pair up and not overflow
+        if seeds.len() % 2 == 1 {
+            return Err(ParseError::new(
+                Self::DAY,
+                0,
+                first_line,
+                seed_spans[seeds.len() - 1].clone(),
+                "seeds in pairs of start and length, but this start has no length",
+            ));
+        }
+        for (pair, spans) in seeds.chunks_exact(2).zip(seed_spans.chunks_exact(2)) {
+            if pair[0].checked_add(pair[1]).is_none() {
+                return Err(ParseError::new(
+                    Self::DAY,
+                    0,
+                    first_line,
+                    spans[0].start..spans[1].end,
+                    "a seed range ending before 2^64",
+                ));
+            }
+        }
+
+        // Build the maps
+        let n_lines = input.lines().count();
+        let chain = MapChain::parse(lines)?;
+        let seed_to_location = chain.compose("seed", "location").map_err(|_| {
+            ParseError::missing_line(
+                Self::DAY,
+                n_lines,
+                format!(
+                    "maps leading from seed to location, but they only go {}",
+                    chain.categories().join(" -> ")
+                ),
+            )
+        })?;
+
+        Ok(Almanac {
+            seeds,
+            chain,
+            seed_to_location,
+        })
+    }
+
+    fn solve_part1(almanac: &Self::Parsed) -> Result<Self::Answer1, SolveError> {
+        // Find to locations corresponding to seeds
+        let mut locations: Vec<u64> = vec![];
+        for &seed in &almanac.seeds {
+            locations.push(almanac.seed_to_location.get(seed));
+        }
+
+        // Find the lowest location, there being at least one seed
+        locations
+            .into_iter()
+            .min()
+            .ok_or_else(|| SolveError::new(Self::DAY, 1, "There are no seeds."))
+    }
+
+    fn solve_part2(almanac: &Self::Parsed) -> Result<Self::Answer2, SolveError> {
+        let seed_ranges = almanac.seed_ranges();
+
+        // Find to locations corresponding to seeds
+        let loc_ranges: IntervalSet<u64> = seed_ranges
+            .iter()
+            .flat_map(|seed_range| {
+                almanac
+                    .seed_to_location
+                    .src_range_to_dest_ranges(seed_range)
+            })
+            .collect();
+
+        // Find the lowest location, the ranges being possibly all empty
+        loc_ranges
+            .min()
+            .ok_or_else(|| SolveError::new(Self::DAY, 2, "The seed ranges hold no seed."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed_overflow() {
+        let input = include_str!("example.txt").replacen("79 14", "18446744073709551615 5", 1);
+        let err = Day5::parse(&input).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 8));
+        assert_eq!(err.snippet, "18446744073709551615 5");
+    }
+
+    #[test]
+    fn test_seeds() {
+        let input = include_str!("example.txt");
+        let err = Day5::parse(&input.replacen("79 14 55 13", " ", 1)).unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (1, "at least one seed"));
+        let err = Day5::parse(&input.replacen("79 14 55 13", "79 14 55", 1)).unwrap_err();
+        assert_eq!((err.column, err.snippet.as_str()), (14, "55"));
+
+        let almanac = Day5::parse(&input.replacen("79 14 55 13", "79 0", 1)).unwrap();
+        assert_eq!(Day5::solve_part1(&almanac), Ok(22));
+        assert!(Day5::solve_part2(&almanac).is_err());
+    }
+
+    #[test]
+    fn test_upward() {
+        let almanac = Day5::parse(include_str!("example.txt")).unwrap();
+        let seeds: IntervalSet<u64> = almanac.seeds.iter().map(|&s| s..s + 1).collect();
+        assert_eq!(almanac.lowest_location_upward(&seeds), Some(35));
+        assert_eq!(
+            almanac.lowest_location_upward(&almanac.seed_ranges()),
+            Some(46)
+        );
+        assert_eq!(almanac.lowest_location_upward(&IntervalSet::new()), None);
+
+        let sources = almanac.chain().sources("seed", "location", 46).unwrap();
+        assert!(sources.contains(82));
+        assert!(sources.iter().all(|range| range
+            .clone()
+            .all(|seed| almanac.seed_to_location().get(seed) == 46)));
+    }
+}
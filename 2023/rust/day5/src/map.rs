@@ -0,0 +1,207 @@
+use std::ops::Range;
+
+use crate::interval_set::IntervalSet;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeProduct {
+    pub src: Range<u64>,
+    pub dest: Range<u64>,
+}
+
+/// Ranges of source values, each shifted to a range of destination values of
+/// the same length. Values outside of every range map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    /// Sorted by source, and covering all values (see [`fill`]).
+    ranges: Vec<RangeProduct>,
+}
+
+impl Map {
+    pub fn new(mut ranges: Vec<RangeProduct>) -> Map {
+        // Sort the map using the start of the src range
+        ranges.sort_by_key(|range| range.src.start);
+        Map {
+            ranges: fill(&ranges),
+        }
+    }
+
+    /// All the ranges, including the ones mapping values to themselves.
+    pub fn ranges(&self) -> &[RangeProduct] {
+        &self.ranges
+    }
+
+    /// The number of ranges, including the ones mapping values to themselves.
+    pub fn n_segments(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Index of the range containing `src_val`, if any.
+    fn find(&self, src_val: u64) -> Option<usize> {
+        let idx = self.ranges.partition_point(|rp| rp.src.end <= src_val);
+        (idx < self.ranges.len() && self.ranges[idx].src.contains(&src_val)).then_some(idx)
+    }
+
+    pub fn get(&self, src_val: u64) -> u64 {
+        match self.find(src_val) {
+            Some(idx) => {
+                let rp = &self.ranges[idx];
+                rp.dest.start + (src_val - rp.src.start)
+            }
+            None => src_val,
+        }
+    }
+
+    pub fn src_range_to_dest_ranges(&self, src_range: &Range<u64>) -> Vec<Range<u64>> {
+        self.overlaps(src_range).map(|(_, dest)| dest).collect()
+    }
+
+    /// The values mapped into `dest_range`.
+    ///
+    /// Several ranges can map to overlapping destinations, so there can be many.
+    pub fn dest_range_to_src_ranges(&self, dest_range: &Range<u64>) -> IntervalSet<u64> {
+        self.ranges
+            .iter()
+            .filter_map(|rp| {
+                let start = rp.dest.start.max(dest_range.start);
+                let end = rp.dest.end.min(dest_range.end);
+                let src_start = rp.src.start + (start - rp.dest.start);
+                (start < end).then(|| src_start..src_start + (end - start))
+            })
+            .collect()
+    }
+
+    /// The parts of `src_range` falling in each range of the map, in order,
+    /// along with where they are mapped to.
+    fn overlaps<'a>(
+        &'a self,
+        src_range: &'a Range<u64>,
+    ) -> impl Iterator<Item = (Range<u64>, Range<u64>)> + 'a {
+        let first = self
+            .ranges
+            .partition_point(|rp| rp.src.end <= src_range.start);
+        self.ranges[first..]
+            .iter()
+            .take_while(|rp| rp.src.start < src_range.end)
+            .filter(|_| !src_range.is_empty())
+            .map(|rp| {
+                let start = rp.src.start.max(src_range.start);
+                let end = rp.src.end.min(src_range.end);
+                let dest_start = rp.dest.start + (start - rp.src.start);
+                (start..end, dest_start..dest_start + (end - start))
+            })
+    }
+
+    /// The map equivalent to going through `self`, then through `next`.
+    pub fn compose(&self, next: &Map) -> Map {
+        let mut ranges: Vec<RangeProduct> = vec![];
+        for rp in &self.ranges {
+            for (next_src, next_dest) in next.overlaps(&rp.dest) {
+                let src_start = rp.src.start + (next_src.start - rp.dest.start);
+                let src = src_start..src_start + (next_src.end - next_src.start);
+                ranges.push(RangeProduct {
+                    src,
+                    dest: next_dest,
+                });
+            }
+        }
+        ranges.sort_by_key(|range| range.src.start);
+
+        // Merge the neighbouring ranges shifted by the same amount
+        let mut merged: Vec<RangeProduct> = vec![];
+        for rp in ranges {
+            match merged.last_mut() {
+                Some(last) if last.src.end == rp.src.start && last.dest.end == rp.dest.start => {
+                    last.src.end = rp.src.end;
+                    last.dest.end = rp.dest.end;
+                }
+                _ => merged.push(rp),
+            }
+        }
+
+        Map {
+            ranges: fill(&merged),
+        }
+    }
+}
+
+/// Adds identity ranges between the sorted `ranges`, so that every value
+/// belongs to a range.
+fn fill(map: &[RangeProduct]) -> Vec<RangeProduct> {
+    let mapped: IntervalSet<u64> = map.iter().map(|rp| rp.src.clone()).collect();
+    let gaps = IntervalSet::from(u64::MIN..u64::MAX).difference(&mapped);
+
+    // Nothing is mapped in the gaps, so they map to themselves
+    let mut new_map: Vec<RangeProduct> = map
+        .iter()
+        .cloned()
+        .chain(gaps.iter().map(|gap| RangeProduct {
+            src: gap.clone(),
+            dest: gap.clone(),
+        }))
+        .collect();
+    new_map.sort_by_key(|range| range.src.start);
+
+    new_map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(ranges: &[(u64, u64, u64)]) -> Map {
+        Map::new(
+            ranges
+                .iter()
+                .map(|&(dest, src, len)| RangeProduct {
+                    src: src..src + len,
+                    dest: dest..dest + len,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_get() {
+        let m = map(&[(50, 98, 2), (52, 50, 48)]);
+        assert_eq!(m.get(0), 0);
+        assert_eq!(m.get(49), 49);
+        assert_eq!(m.get(50), 52);
+        assert_eq!(m.get(97), 99);
+        assert_eq!(m.get(98), 50);
+        assert_eq!(m.get(99), 51);
+        assert_eq!(m.get(100), 100);
+        assert_eq!(m.get(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_compose() {
+        let first = map(&[(50, 98, 2), (52, 50, 48)]);
+        let second = map(&[(0, 15, 37), (37, 52, 2), (39, 0, 15)]);
+        let composed = first.compose(&second);
+        for n in 0..200 {
+            assert_eq!(composed.get(n), second.get(first.get(n)));
+        }
+        assert_eq!(
+            composed.src_range_to_dest_ranges(&(40..60)),
+            [25..35, 37..39, 54..62]
+        );
+
+        assert_eq!(
+            composed.dest_range_to_src_ranges(&(37..39)),
+            IntervalSet::from(50..52)
+        );
+        assert_eq!(
+            first.dest_range_to_src_ranges(&(50..53)).ranges(),
+            [50..51, 98..100]
+        );
+        assert_eq!(
+            first.dest_range_to_src_ranges(&(99..101)).ranges(),
+            [97..98, 100..101]
+        );
+
+        // Identities vanish in the composition
+        assert_eq!(Map::new(vec![]).compose(&first), first);
+        assert_eq!(first.compose(&Map::new(vec![])), first);
+        assert_eq!(Map::new(vec![]).compose(&Map::new(vec![])).n_segments(), 1);
+    }
+}
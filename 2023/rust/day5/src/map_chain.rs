@@ -0,0 +1,388 @@
+use std::{collections::HashMap, error, fmt, ops::Range};
+
+use common::{ParseError, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    interval_set::IntervalSet,
+    map::{Map, RangeProduct},
+    Day5, NUM_RE,
+};
+
+lazy_static! {
+    static ref MAP_RE: Regex = Regex::new(r"^(?<src>\w+)-to-(?<dest>\w+) map:$").unwrap();
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    UnknownCategory(String),
+    /// `to` comes before `from` in the chain.
+    Unreachable {
+        from: String,
+        to: String,
+    },
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::UnknownCategory(category) => {
+                write!(f, "There is no map from or to '{category}'.")
+            }
+            ChainError::Unreachable { from, to } => {
+                write!(f, "'{to}' cannot be reached from '{from}'.")
+            }
+        }
+    }
+}
+
+impl error::Error for ChainError {}
+
+/// A `X-to-Y map:` section of the input, before it is put in the chain.
+struct Section<'a> {
+    src: String,
+    dest: String,
+    /// The header of the section, with its index in the input.
+    header: (usize, &'a str),
+    ranges: Vec<RangeProduct>,
+}
+
+impl Section<'_> {
+    fn error(&self, expected: impl Into<String>) -> ParseError {
+        let (i, line) = self.header;
+        ParseError::line(Day5::DAY, i, line, expected)
+    }
+}
+
+/// Maps from category to category, each one picking up where the previous one
+/// left off, so that a value of any category can be followed down the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapChain {
+    /// In chain order: `maps[i]` goes from `categories[i]` to `categories[i + 1]`.
+    categories: Vec<String>,
+    maps: Vec<Map>,
+    positions: HashMap<String, usize>,
+}
+
+impl MapChain {
+    /// Parses `X-to-Y map:` sections, given as lines with their index in the
+    /// input, into a chain.
+    ///
+    /// The sections can come in any order, but must form a single chain: no
+    /// category can be mapped from twice, and there can be no gaps or cycles.
+    pub fn parse<'a>(
+        lines: impl Iterator<Item = (usize, &'a str)>,
+    ) -> Result<MapChain, ParseError> {
+        let mut sections: Vec<Section> = vec![];
+
+        for (i, line) in lines {
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with(|c: char| c.is_alphabetic()) {
+                // We are beginning a new map
+                let caps = MAP_RE.captures(line).ok_or_else(|| {
+                    ParseError::line(Day5::DAY, i, line, "a map header, like 'seed-to-soil map:'")
+                })?;
+                sections.push(Section {
+                    src: caps["src"].to_string(),
+                    dest: caps["dest"].to_string(),
+                    header: (i, line),
+                    ranges: vec![],
+                });
+                continue;
+            }
+
+            // At this point, line must be a range specification
+            let section = sections.last_mut().ok_or_else(|| {
+                ParseError::line(Day5::DAY, i, line, "a map header, like 'seed-to-soil map:'")
+            })?;
+
+            let ns: Vec<u64> = NUM_RE
+                .find_iter(line)
+                .map(|n| ParseError::number(Day5::DAY, i, line, n.range()))
+                .collect::<Result<_, _>>()?;
+
+            if ns.len() != 3 || !line.chars().all(|c| c.is_ascii_digit() || c == ' ') {
+                return Err(ParseError::line(
+                    Day5::DAY,
+                    i,
+                    line,
+                    "a range, as '<destination start> <source start> <length>'",
+                ));
+            }
+
+            let dest_range_start = ns[0];
+            let src_range_start = ns[1];
+            let range_len = ns[2];
+            let (Some(src_range_end), Some(dest_range_end)) = (
+                src_range_start.checked_add(range_len),
+                dest_range_start.checked_add(range_len),
+            ) else {
+                return Err(ParseError::line(
+                    Day5::DAY,
+                    i,
+                    line,
+                    "a range ending before 2^64",
+                ));
+            };
+            section.ranges.push(RangeProduct {
+                src: Range {
+                    start: src_range_start,
+                    end: src_range_end,
+                },
+                dest: Range {
+                    start: dest_range_start,
+                    end: dest_range_end,
+                },
+            });
+        }
+
+        MapChain::link(sections)
+    }
+
+    /// Puts the sections in chain order, checking that they form one chain.
+    fn link(sections: Vec<Section>) -> Result<MapChain, ParseError> {
+        let Some(first) = sections.first() else {
+            return Err(ParseError::missing_line(
+                Day5::DAY,
+                0,
+                "at least one map, like 'seed-to-soil map:'",
+            ));
+        };
+
+        let mut by_src: HashMap<&str, usize> = HashMap::new();
+        for (idx, section) in sections.iter().enumerate() {
+            if let Some(&other) = by_src.get(section.src.as_str()) {
+                return Err(section.error(format!(
+                    "a single map from '{}', but it already maps to '{}'",
+                    section.src, sections[other].dest
+                )));
+            }
+            by_src.insert(&section.src, idx);
+        }
+
+        // The chain starts at the only category that nothing maps to
+        let start = sections
+            .iter()
+            .find(|section| !sections.iter().any(|other| other.dest == section.src))
+            .ok_or_else(|| {
+                first.error(format!(
+                    "a chain of maps with a start, but all the maps form a cycle through '{}'",
+                    first.src
+                ))
+            })?;
+
+        let mut categories: Vec<String> = vec![start.src.clone()];
+        let mut positions: HashMap<String, usize> = HashMap::from([(start.src.clone(), 0)]);
+        let mut order: Vec<usize> = vec![];
+        let mut cur = by_src.get(start.src.as_str()).copied();
+        while let Some(idx) = cur {
+            let section = &sections[idx];
+            if positions.contains_key(&section.dest) {
+                return Err(section.error(format!(
+                    "a map to a category not already in the chain, but '{}' forms a cycle: {} -> {}",
+                    section.dest,
+                    categories.join(" -> "),
+                    section.dest
+                )));
+            }
+
+            positions.insert(section.dest.clone(), categories.len());
+            categories.push(section.dest.clone());
+            order.push(idx);
+            cur = by_src.get(section.dest.as_str()).copied();
+        }
+
+        if let Some(section) = sections
+            .iter()
+            .enumerate()
+            .find(|(idx, _)| !order.contains(idx))
+            .map(|(_, section)| section)
+        {
+            return Err(section.error(format!(
+                "a map continuing the chain {}, but there is a gap between '{}' and '{}'",
+                categories.join(" -> "),
+                categories.last().unwrap(),
+                section.src
+            )));
+        }
+
+        let mut sections: Vec<Option<Section>> = sections.into_iter().map(Some).collect();
+        let maps = order
+            .into_iter()
+            .map(|idx| Map::new(sections[idx].take().unwrap().ranges))
+            .collect();
+
+        Ok(MapChain {
+            categories,
+            maps,
+            positions,
+        })
+    }
+
+    /// The categories, in chain order.
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    /// The maps to go through to get from `from` to `to`.
+    pub fn path(&self, from: &str, to: &str) -> Result<&[Map], ChainError> {
+        let position = |category: &str| {
+            self.positions
+                .get(category)
+                .copied()
+                .ok_or_else(|| ChainError::UnknownCategory(category.to_string()))
+        };
+        let (i, j) = (position(from)?, position(to)?);
+        if j < i {
+            return Err(ChainError::Unreachable {
+                from: from.to_string(),
+                to: to.to_string(),
+            });
+        }
+
+        Ok(&self.maps[i..j])
+    }
+
+    /// Follows `value` of category `from` down the chain to category `to`.
+    pub fn convert(&self, from: &str, to: &str, value: u64) -> Result<u64, ChainError> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(value, |value, map| map.get(value)))
+    }
+
+    /// The values of category `from` leading to `value` of category `to`.
+    pub fn sources(
+        &self,
+        from: &str,
+        to: &str,
+        value: u64,
+    ) -> Result<IntervalSet<u64>, ChainError> {
+        self.source_ranges(from, to, &IntervalSet::from(value..value.saturating_add(1)))
+    }
+
+    /// The values of category `from` leading to `values` of category `to`.
+    pub fn source_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        values: &IntervalSet<u64>,
+    ) -> Result<IntervalSet<u64>, ChainError> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .rev()
+            .fold(values.clone(), |values, map| {
+                values
+                    .iter()
+                    .map(|range| map.dest_range_to_src_ranges(range))
+                    .fold(IntervalSet::new(), |acc, src_values| acc.union(&src_values))
+            }))
+    }
+
+    /// The single map going from `from` to `to`, equivalent to the whole path.
+    pub fn compose(&self, from: &str, to: &str) -> Result<Map, ChainError> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(Map::new(vec![]), |composed, map| composed.compose(map)))
+    }
+
+    /// Follows `values` of category `from` down the chain to category `to`.
+    pub fn convert_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        values: &IntervalSet<u64>,
+    ) -> Result<IntervalSet<u64>, ChainError> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(values.clone(), |values, map| {
+                values
+                    .iter()
+                    .flat_map(|range| map.src_range_to_dest_ranges(range))
+                    .collect()
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<MapChain, ParseError> {
+        MapChain::parse(input.lines().enumerate())
+    }
+
+    #[test]
+    fn test_any_order() {
+        let chain =
+            parse("b-to-c map:\n10 0 5\n\na-to-b map:\n0 100 5\n\nc-to-d map:\n20 10 1\n").unwrap();
+        assert_eq!(chain.categories(), ["a", "b", "c", "d"]);
+        assert_eq!(chain.convert("a", "d", 100), Ok(20));
+        assert_eq!(chain.convert("b", "c", 3), Ok(13));
+        assert_eq!(chain.convert("b", "b", 3), Ok(3));
+        let composed = chain.compose("a", "d").unwrap();
+        assert_eq!(composed.get(100), 20);
+        assert_eq!(composed.n_segments(), 8);
+        assert_eq!(
+            chain.sources("a", "d", 20).unwrap().ranges(),
+            [0..1, 10..11, 20..21, 100..101]
+        );
+        assert_eq!(
+            chain.sources("c", "d", 20).unwrap().ranges(),
+            [10..11, 20..21]
+        );
+        assert_eq!(
+            chain.sources("a", "c", 12).unwrap().ranges(),
+            [2..3, 12..13, 102..103]
+        );
+        assert_eq!(
+            chain
+                .convert_ranges("a", "d", &[0..12, 100..102].into_iter().collect())
+                .unwrap()
+                .ranges(),
+            [5..10, 11..15, 20..21]
+        );
+        assert_eq!(
+            chain.convert("c", "a", 3),
+            Err(ChainError::Unreachable {
+                from: "c".into(),
+                to: "a".into()
+            })
+        );
+        assert_eq!(
+            chain.convert("a", "z", 3),
+            Err(ChainError::UnknownCategory("z".into()))
+        );
+    }
+
+    #[test]
+    fn test_gap() {
+        let err = parse("a-to-b map:\n0 1 1\n\nc-to-d map:\n0 1 1\n").unwrap_err();
+        assert_eq!(err.line, 4);
+        assert!(err.expected.contains("gap between 'b' and 'c'"));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let err = parse("a-to-b map:\nb-to-c map:\nc-to-b map:\n").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(err.expected.contains("a -> b -> c -> b"));
+
+        let err = parse("a-to-b map:\nb-to-a map:\n").unwrap_err();
+        assert!(err.expected.contains("cycle"));
+    }
+
+    #[test]
+    fn test_branch() {
+        let err = parse("a-to-b map:\na-to-c map:\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(err.expected.contains("already maps to 'b'"));
+    }
+}
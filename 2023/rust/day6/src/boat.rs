@@ -0,0 +1,201 @@
+use std::ops::Range;
+
+use crate::{winning_holds, Distance, Race, Speed, Time};
+
+/// How the speed of a boat grows with the time spent charging it.
+#[derive(Debug, Clone, Copy)]
+pub enum ChargeCurve {
+    /// Gains this speed (mm/ms) per ms charged.
+    Linear(Speed),
+    /// Gains this speed (mm/ms) times the square of the ms charged.
+    Quadratic(Speed),
+    /// Any other curve, from the ms charged to the speed (mm/ms).
+    Custom(fn(Time) -> Speed),
+}
+
+/// A boat charged by holding its button at the start of a race, then let go.
+#[derive(Debug, Clone)]
+pub struct ToyBoat {
+    charge: ChargeCurve,
+    max_speed: Option<Speed>, // mm/ms
+    /// The first ms of the hold, spent before the boat starts charging.
+    charge_delay: Time, // ms
+    /// The speed lost every ms once let go, until the boat stops.
+    deceleration: Speed, // mm/ms
+}
+
+impl Default for ToyBoat {
+    fn default() -> ToyBoat {
+        ToyBoat::new()
+    }
+}
+
+impl ToyBoat {
+    /// The boat of the puzzle, gaining 1 mm/ms per ms held, with no limits.
+    pub fn new() -> ToyBoat {
+        ToyBoat {
+            charge: ChargeCurve::Linear(1),
+            max_speed: None,
+            charge_delay: 0,
+            deceleration: 0,
+        }
+    }
+
+    pub fn with_charge(mut self, charge: ChargeCurve) -> ToyBoat {
+        self.charge = charge;
+        self
+    }
+
+    pub fn with_max_speed(mut self, max_speed: Speed) -> ToyBoat {
+        self.max_speed = Some(max_speed);
+        self
+    }
+
+    pub fn with_charge_delay(mut self, charge_delay: Time) -> ToyBoat {
+        self.charge_delay = charge_delay;
+        self
+    }
+
+    pub fn with_deceleration(mut self, deceleration: Speed) -> ToyBoat {
+        self.deceleration = deceleration;
+        self
+    }
+
+    /// The speed of the boat when let go after `hold` ms.
+    pub fn speed(&self, hold: Time) -> u128 {
+        let charged = hold.saturating_sub(self.charge_delay) as u128;
+        let speed = match self.charge {
+            ChargeCurve::Linear(gain) => gain as u128 * charged,
+            ChargeCurve::Quadratic(gain) => (gain as u128).saturating_mul(charged * charged),
+            ChargeCurve::Custom(curve) => curve(charged as Time) as u128,
+        };
+
+        match self.max_speed {
+            Some(max_speed) => speed.min(max_speed as u128),
+            None => speed,
+        }
+    }
+
+    /// The distance travelled when holding the button for `hold` ms, saturating
+    /// at the largest distance.
+    pub fn run_race(&self, race: &Race, hold: Time) -> Distance {
+        let Some(remaining_time) = race.time.checked_sub(hold) else {
+            // The race is over before the boat is let go
+            return 0;
+        };
+        let remaining_time = remaining_time as u128;
+        let speed = self.speed(hold);
+
+        let distance = if self.deceleration == 0 {
+            speed.saturating_mul(remaining_time)
+        } else {
+            // Moves at `speed`, then `speed - deceleration`, ... while still moving
+            let deceleration = self.deceleration as u128;
+            let n_moving = remaining_time.min(speed.div_ceil(deceleration));
+            speed.saturating_mul(n_moving)
+                - deceleration.saturating_mul(n_moving * n_moving.saturating_sub(1) / 2)
+        };
+
+        distance.min(Distance::MAX as u128) as Distance
+    }
+
+    /// The holds beating the record, as disjoint ranges in order.
+    ///
+    /// Computed in closed form for boats charging linearly with no limits, and
+    /// by trying every hold otherwise.
+    pub fn winning_holds(&self, race: &Race) -> Vec<Range<Time>> {
+        match self.charge {
+            ChargeCurve::Linear(0) => vec![],
+            ChargeCurve::Linear(gain) if self.max_speed.is_none() && self.deceleration == 0 => {
+                // gain * charged * (time - hold) > record, charged being hold - delay
+                let Some(charging_time) = race.time.checked_sub(self.charge_delay) else {
+                    return vec![];
+                };
+                let charged =
+                    winning_holds(charging_time as u128, (race.record_distance / gain) as u128);
+                let holds = charged.start as Time + self.charge_delay
+                    ..charged.end as Time + self.charge_delay;
+                if holds.is_empty() {
+                    vec![]
+                } else {
+                    vec![holds]
+                }
+            }
+            _ => self.try_every_hold(race),
+        }
+    }
+
+    fn try_every_hold(&self, race: &Race) -> Vec<Range<Time>> {
+        let mut holds: Vec<Range<Time>> = vec![];
+        for hold in 0..=race.time {
+            if self.run_race(race, hold) <= race.record_distance {
+                continue;
+            }
+            match holds.last_mut() {
+                Some(last) if last.end == hold => last.end = hold + 1,
+                _ => holds.push(hold..hold + 1),
+            }
+        }
+
+        holds
+    }
+
+    /// The number of holds beating the record.
+    pub fn n_winning_holds(&self, race: &Race) -> u64 {
+        self.winning_holds(race)
+            .iter()
+            .map(|holds| holds.end - holds.start)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closed_form() {
+        for boat in [
+            ToyBoat::new(),
+            ToyBoat::new().with_charge(ChargeCurve::Linear(3)),
+            ToyBoat::new().with_charge_delay(4),
+            ToyBoat::new()
+                .with_charge(ChargeCurve::Linear(2))
+                .with_charge_delay(1),
+        ] {
+            for time in 0..30 {
+                for record_distance in 0..(time * time + 2) {
+                    let race = Race::new(time, record_distance);
+                    assert_eq!(boat.winning_holds(&race), boat.try_every_hold(&race));
+                }
+            }
+        }
+        assert!(ToyBoat::new()
+            .with_charge(ChargeCurve::Linear(0))
+            .winning_holds(&Race::new(10, 0))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_physics() {
+        let race = Race::new(10, 20);
+
+        let boat = ToyBoat::new().with_max_speed(3);
+        assert_eq!(boat.run_race(&race, 2), 16);
+        assert_eq!(boat.run_race(&race, 5), 15);
+        assert_eq!(boat.winning_holds(&race), vec![3..4]);
+
+        let boat = ToyBoat::new().with_charge(ChargeCurve::Quadratic(1));
+        assert_eq!(boat.run_race(&race, 3), 63);
+        assert_eq!(boat.n_winning_holds(&race), 8);
+
+        // 5 + 3 + 1, then stopped
+        let boat = ToyBoat::new().with_deceleration(2);
+        assert_eq!(boat.run_race(&race, 5), 9);
+        assert_eq!(boat.run_race(&race, 11), 0);
+
+        // Charging only works every other ms
+        let boat = ToyBoat::new().with_charge(ChargeCurve::Custom(|charged| charged % 2 * 10));
+        assert_eq!(boat.winning_holds(&race), [1..2, 3..4, 5..6, 7..8]);
+    }
+}
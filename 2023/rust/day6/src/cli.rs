@@ -0,0 +1,173 @@
+use std::process::ExitCode;
+
+use common::{input, Solution};
+use num_bigint::BigUint;
+
+use crate::{
+    boat::{ChargeCurve, ToyBoat},
+    kerned_digits, Day6, Race, Speed, Time,
+};
+
+const USAGE: &str = "Usage: day6-partN [INPUT] [--linear GAIN | --quadratic GAIN]
+                   [--max-speed SPEED] [--charge-delay MS] [--deceleration SPEED]
+
+The boat gains GAIN mm/ms per ms held with --linear, or GAIN times the square
+of the ms held with --quadratic, never going faster than --max-speed mm/ms. It
+only starts charging after --charge-delay ms, and loses --deceleration mm/ms
+every ms once let go. Without any of these, the boat of the puzzle is used.";
+
+#[derive(Debug, Default)]
+struct Args {
+    input: Option<String>,
+    charge: Option<ChargeCurve>,
+    max_speed: Option<Speed>,
+    charge_delay: Option<Time>,
+    deceleration: Option<Speed>,
+}
+
+impl Args {
+    /// The boat described by the flags, if any was given.
+    fn boat(&self) -> Option<ToyBoat> {
+        if self.charge.is_none()
+            && self.max_speed.is_none()
+            && self.charge_delay.is_none()
+            && self.deceleration.is_none()
+        {
+            return None;
+        }
+
+        let mut boat = ToyBoat::new();
+        if let Some(charge) = self.charge {
+            boat = boat.with_charge(charge);
+        }
+        if let Some(max_speed) = self.max_speed {
+            boat = boat.with_max_speed(max_speed);
+        }
+        if let Some(charge_delay) = self.charge_delay {
+            boat = boat.with_charge_delay(charge_delay);
+        }
+        if let Some(deceleration) = self.deceleration {
+            boat = boat.with_deceleration(deceleration);
+        }
+        Some(boat)
+    }
+}
+
+/// Sets `value` to the number following `flag`, if it was not set already.
+fn set_number(
+    value: &mut Option<u64>,
+    flag: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<(), String> {
+    if value.is_some() {
+        return Err(format!("'{flag}' can only be given once."));
+    }
+    let n = args
+        .next()
+        .ok_or_else(|| format!("Expected a number after '{flag}'."))?;
+    let n = n
+        .parse()
+        .map_err(|_| format!("Expected a number after '{flag}', got '{n}'."))?;
+    *value = Some(n);
+
+    Ok(())
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--linear" | "--quadratic" if parsed.charge.is_some() => {
+                return Err("The charge curve can only be given once.".into());
+            }
+            "--linear" | "--quadratic" => {
+                let mut gain = None;
+                set_number(&mut gain, &arg, &mut args)?;
+                parsed.charge = gain.map(|gain| match arg.as_str() {
+                    "--linear" => ChargeCurve::Linear(gain),
+                    _ => ChargeCurve::Quadratic(gain),
+                });
+            }
+            "--max-speed" => set_number(&mut parsed.max_speed, &arg, &mut args)?,
+            "--charge-delay" => set_number(&mut parsed.charge_delay, &arg, &mut args)?,
+            "--deceleration" => set_number(&mut parsed.deceleration, &arg, &mut args)?,
+            _ if parsed.input.is_none() => parsed.input = Some(arg),
+            _ => return Err(format!("Unexpected argument '{arg}'.")),
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Entry point of the day 6 binaries, which take the boat from the command
+/// line.
+pub fn run_part(part: u8) -> ExitCode {
+    common::run_with(USAGE, parse_args, |args| answer(args, part))
+}
+
+fn answer(args: &Args, part: u8) -> Result<String, String> {
+    let input = input::read(Day6::DAY, args.input.as_deref()).map_err(|e| e.to_string())?;
+    let races = Day6::parse(&input).map_err(|e| e.to_string())?;
+
+    let Some(boat) = args.boat() else {
+        return Ok(match part {
+            1 => Day6::solve_part1(&races)
+                .map_err(|e| e.to_string())?
+                .to_string(),
+            _ => Day6::solve_part2(&races)
+                .map_err(|e| e.to_string())?
+                .to_string(),
+        });
+    };
+
+    Ok(match part {
+        1 => races
+            .iter()
+            .map(|race| BigUint::from(boat.n_winning_holds(race)))
+            .product::<BigUint>()
+            .to_string(),
+        _ => {
+            // Only the puzzle's boat can race for longer than fits in a u64
+            let (time, distance) = kerned_digits(&races);
+            let (Ok(time), Ok(distance)) = (time.parse(), distance.parse()) else {
+                return Err(format!(
+                    "The race of {time} ms with a record of {distance} mm is too long for any \
+                     boat but the puzzle's."
+                ));
+            };
+            boat.n_winning_holds(&Race::new(time, distance)).to_string()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let args = |args: &str| parse_args(args.split_whitespace().map(String::from));
+
+        let parsed = args("in.txt --quadratic 2 --max-speed 30 --deceleration 1").unwrap();
+        assert_eq!(parsed.input.as_deref(), Some("in.txt"));
+        let boat = parsed.boat().unwrap();
+        assert_eq!(boat.speed(3), 18);
+        assert_eq!(boat.speed(4), 30);
+        assert_eq!(
+            boat.run_race(&Race::new(10, 0), 4),
+            30 + 29 + 28 + 27 + 26 + 25
+        );
+        assert!(args("").unwrap().boat().is_none());
+
+        assert!(args("--linear 2 --quadratic 1").is_err());
+        assert!(args("--charge-delay 1 --charge-delay 2").is_err());
+        assert_eq!(
+            args("--max-speed fast").unwrap_err(),
+            "Expected a number after '--max-speed', got 'fast'."
+        );
+        assert!(args("--deceleration").is_err());
+        assert!(args("a.txt b.txt").is_err());
+    }
+}
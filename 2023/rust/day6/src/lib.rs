@@ -0,0 +1,255 @@
+use common::{ParseError, Solution, SolveError};
+use lazy_static::lazy_static;
+use num_bigint::BigUint;
+use num_integer::{Integer, Roots};
+use regex::Regex;
+use std::{iter::zip, ops::Range};
+
+pub mod boat;
+pub mod cli;
+
+lazy_static! {
+    static ref TIME_RE: Regex = Regex::new(r"^Time: *(?<times>(\d| )+)$").unwrap();
+    static ref DISTANCE_RE: Regex = Regex::new(r"^Distance: *(?<distances>(\d| )+)$").unwrap();
+    static ref NUM_RE: Regex = Regex::new(r"(?<n>\d+)").unwrap();
+}
+
+type Time = u64;
+type Distance = u64;
+type Speed = u64;
+
+#[derive(Debug)]
+pub struct Race {
+    time: Time,                // ms
+    record_distance: Distance, // mm
+}
+
+impl Race {
+    pub fn new(time: Time, record_distance: Distance) -> Race {
+        Race {
+            time,
+            record_distance,
+        }
+    }
+
+    /// The holds beating the record, for a boat gaining 1 mm/ms per ms held.
+    ///
+    /// See [`boat::ToyBoat::winning_holds`] for other boats.
+    pub fn winning_hold_range(&self) -> Range<Time> {
+        // `time * time` always fits in a u128
+        let holds = winning_holds(self.time as u128, self.record_distance as u128);
+        holds.start as Time..holds.end as Time
+    }
+}
+
+/// A race too long for its time or record to fit in a u64.
+#[derive(Debug)]
+pub struct BigRace {
+    time: BigUint,            // ms
+    record_distance: BigUint, // mm
+}
+
+impl BigRace {
+    pub fn new(time: BigUint, record_distance: BigUint) -> BigRace {
+        BigRace {
+            time,
+            record_distance,
+        }
+    }
+
+    /// The holds beating the record, for a boat gaining 1 mm/ms per ms held.
+    pub fn winning_hold_range(&self) -> Range<BigUint> {
+        winning_holds(self.time.clone(), self.record_distance.clone())
+    }
+}
+
+/// The holds `h` such that `h * (time - h) > record`, i.e. strictly between the
+/// roots of `h^2 - time * h + record`, found with integer square roots only.
+fn winning_holds<T: Integer + Roots + Clone>(time: T, record: T) -> Range<T> {
+    let two = T::one() + T::one();
+    let four = two.clone() * two.clone();
+    let wins = |hold: &T| hold.clone() * (time.clone() - hold.clone()) > record;
+    let half = time.clone() / two.clone();
+
+    // Without two distinct roots, no hold does better than the record
+    let square = time.clone() * time.clone();
+    let four_record = four * record.clone();
+    if square <= four_record {
+        return T::zero()..T::zero();
+    }
+    let root = (square - four_record).sqrt();
+
+    // The square root is rounded down, so the first winning hold is close by
+    let mut first = (time.clone() - root) / two;
+    while first > T::zero() && wins(&(first.clone() - T::one())) {
+        first = first - T::one();
+    }
+    while first <= half && !wins(&first) {
+        first = first + T::one();
+    }
+    if first > half {
+        return T::zero()..T::zero();
+    }
+
+    // The distances are symmetric around half the time
+    let last = time - first.clone();
+    first..last + T::one()
+}
+
+/// The time and record of the only race there actually is, the spaces between
+/// the digits of the races being bad kerning, which may not fit in a u64.
+fn kerned_digits(races: &[Race]) -> (String, String) {
+    let mut time = String::new();
+    let mut distance = String::new();
+    for race in races {
+        time.push_str(&race.time.to_string());
+        distance.push_str(&race.record_distance.to_string());
+    }
+    (time, distance)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Parsed = Vec<Race>;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let lines: Vec<&str> = input.lines().collect();
+
+        // Parses all the numbers of a line, after its label
+        let numbers = |i: usize, re: &Regex, group: &str, expected: &str| {
+            let line = *lines
+                .get(i)
+                .ok_or_else(|| ParseError::missing_line(Self::DAY, lines.len(), expected))?;
+            let caps = re
+                .captures(line)
+                .ok_or_else(|| ParseError::line(Self::DAY, i, line, expected))?;
+            let group = caps.name(group).unwrap();
+            NUM_RE
+                .find_iter(group.as_str())
+                .map(|n| {
+                    ParseError::number::<u64>(
+                        Self::DAY,
+                        i,
+                        line,
+                        group.start() + n.start()..group.start() + n.end(),
+                    )
+                })
+                .collect::<Result<Vec<u64>, ParseError>>()
+        };
+
+        // Parse the races
+        let times = numbers(0, &TIME_RE, "times", "the times, like 'Time: 7 15'")?;
+        let distances = numbers(
+            1,
+            &DISTANCE_RE,
+            "distances",
+            "the distances, like 'Distance: 9 40'",
+        )?;
+
+        if times.is_empty() {
+            return Err(ParseError::line(
+                Self::DAY,
+                0,
+                lines[0],
+                "at least one race, like 'Time: 7 15'",
+            ));
+        }
+        if times.len() != distances.len() {
+            return Err(ParseError::line(
+                Self::DAY,
+                1,
+                lines[1],
+                format!("as many distances as times ({})", times.len()),
+            ));
+        }
+        Ok(zip(&times, &distances)
+            .map(|(&t, &d)| Race::new(t, d))
+            .collect())
+    }
+
+    fn solve_part1(races: &Self::Parsed) -> Result<Self::Answer1, SolveError> {
+        // Find all the ways to do better than the records, which multiplied
+        // together overflow with several long races
+        Ok(races
+            .iter()
+            .map(|race| {
+                let holds = race.winning_hold_range();
+                BigUint::from(holds.end - holds.start)
+            })
+            .product())
+    }
+
+    fn solve_part2(races: &Self::Parsed) -> Result<Self::Answer2, SolveError> {
+        let (time, distance) = kerned_digits(races);
+
+        // Find the number of ways to do better than the record
+        Ok(match (time.parse::<Time>(), distance.parse::<Distance>()) {
+            (Ok(time), Ok(distance)) => {
+                let holds = Race::new(time, distance).winning_hold_range();
+                BigUint::from(holds.end - holds.start)
+            }
+            // The digits put together overflow
+            _ => {
+                let holds = BigRace::new(time.parse().unwrap(), distance.parse().unwrap())
+                    .winning_hold_range();
+                holds.end - holds.start
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boat::ToyBoat;
+
+    #[test]
+    fn test_winning_hold_range() {
+        let boat = ToyBoat::new();
+        for time in 0..60 {
+            for record_distance in 0..(time * time / 4 + 2) {
+                let race = Race::new(time, record_distance);
+                let brute_force: Vec<Time> = (0..=time)
+                    .filter(|&hold| boat.run_race(&race, hold) > record_distance)
+                    .collect();
+                let holds = race.winning_hold_range();
+                assert_eq!(holds.clone().collect::<Vec<Time>>(), brute_force);
+            }
+        }
+
+        let holds = Race::new(u64::MAX, u64::MAX - 1).winning_hold_range();
+        assert_eq!(holds, 2..u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_no_races() {
+        let err = Day6::parse("Time: \nDistance: ").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(Day6::parse("Time: 7\nDistance: ").is_err());
+    }
+
+    #[test]
+    fn test_long_races() {
+        let races = Day6::parse("Time: 10000000000 10000000000\nDistance: 0 0").unwrap();
+        let n_holds = BigUint::from(10000000000u64 - 1);
+        assert_eq!(Day6::solve_part1(&races), Ok(n_holds.pow(2)));
+    }
+
+    #[test]
+    fn test_big_race() {
+        // Holding 10^10 ms out of 10^30 ms goes 10^40 - 10^20 mm
+        let time = BigUint::from(10u32).pow(30);
+        let hold = BigUint::from(10u32).pow(10);
+        let record = BigUint::from(10u32).pow(40) - BigUint::from(10u32).pow(20) - 1u32;
+        let holds = BigRace::new(time.clone(), record.clone()).winning_hold_range();
+        assert_eq!(holds, hold.clone()..time.clone() - hold.clone() + 1u32);
+
+        let holds = BigRace::new(time, record + 1u32).winning_hold_range();
+        assert_eq!(holds.start, hold + 1u32);
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+
+use crate::ruleset::{Ruleset, TieBreak};
+
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Ord, Eq, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    /// Only with rules allowing straights.
+    Straight,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandType::HighCard => "high card",
+            HandType::OnePair => "one pair",
+            HandType::TwoPair => "two pair",
+            HandType::ThreeOfAKind => "three of a kind",
+            HandType::Straight => "straight",
+            HandType::FullHouse => "full house",
+            HandType::FourOfAKind => "four of a kind",
+            HandType::FiveOfAKind => "five of a kind",
+        };
+        f.pad(name)
+    }
+}
+
+/// Five cards, ranked by the rules they are played with.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Hand {
+    /// The hand type, then the ranks of the cards in the order they are
+    /// compared in, 4 bits each, so that hands compare as their keys do.
+    key: u32,
+    cards: [char; 5],
+}
+
+impl Hand {
+    pub fn new(cards: &str, rules: &Ruleset) -> Hand {
+        let n_cards = cards.chars().count();
+        assert!(
+            n_cards == 5,
+            "A hand is composed of exactly 5 cards, but got {n_cards}."
+        );
+        let mut chars = cards.chars();
+        let cards: [char; 5] = std::array::from_fn(|_| chars.next().unwrap());
+        let ranks = cards.map(|card| rules.rank(card));
+        let wildcards = cards.map(|card| rules.is_wildcard(card));
+
+        let hand_type = hand_type(&ranks, &wildcards, rules);
+        let key = tie_break_ranks(ranks, rules.tie_break())
+            .iter()
+            .fold(hand_type as u32, |key, &rank| key << 4 | rank as u32);
+
+        Hand { key, cards }
+    }
+
+    pub fn cards(&self) -> &[char; 5] {
+        &self.cards
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        HAND_TYPES[(self.key >> 20) as usize]
+    }
+
+    /// The card the wildcards of the hand stand for, if it has any: the most
+    /// common other card, the strongest among as common ones.
+    ///
+    /// Wildcards completing a straight stand for several cards, so have none.
+    pub fn promotion(&self, rules: &Ruleset) -> Option<char> {
+        let wildcards = self.cards.map(|card| rules.is_wildcard(card));
+        if !wildcards.contains(&true) || self.hand_type() == HandType::Straight {
+            return None;
+        }
+
+        let ranks = self.cards.map(|card| rules.rank(card));
+        let counts = rank_counts(&ranks, &wildcards);
+        let promoted = self
+            .cards
+            .iter()
+            .zip(ranks)
+            .filter(|(&card, _)| !rules.is_wildcard(card))
+            .max_by_key(|(_, rank)| (counts[*rank as usize], *rank))
+            .map(|(&card, _)| card);
+
+        // With only wildcards, any card will do, so take the strongest
+        promoted.or_else(|| rules.strongest_card())
+    }
+
+    /// The packed key hands are sorted by.
+    pub fn key(&self) -> u32 {
+        self.key
+    }
+}
+
+/// The hand types, from the weakest to the strongest, as packed in keys.
+const HAND_TYPES: [HandType; 8] = [
+    HandType::HighCard,
+    HandType::OnePair,
+    HandType::TwoPair,
+    HandType::ThreeOfAKind,
+    HandType::Straight,
+    HandType::FullHouse,
+    HandType::FourOfAKind,
+    HandType::FiveOfAKind,
+];
+
+/// Counts the cards of each rank, leaving out the wildcards.
+fn rank_counts(ranks: &[u8; 5], wildcards: &[bool; 5]) -> [u8; 16] {
+    let mut counts = [0u8; 16];
+    for (&rank, &wildcard) in ranks.iter().zip(wildcards) {
+        if !wildcard {
+            counts[rank as usize] += 1;
+        }
+    }
+
+    counts
+}
+
+fn hand_type(ranks: &[u8; 5], wildcards: &[bool; 5], rules: &Ruleset) -> HandType {
+    let counts = rank_counts(ranks, wildcards);
+    let n_wildcards = wildcards.iter().filter(|&&wildcard| wildcard).count() as u8;
+
+    // The two biggest groups of cards are enough to tell the type
+    let (mut max_count, mut second_count) = (0, 0);
+    for &count in &counts {
+        if count > max_count {
+            second_count = max_count;
+            max_count = count;
+        } else if count > second_count {
+            second_count = count;
+        }
+    }
+
+    // Wildcards make the best hand as more of the most common card
+    let hand_type = match (max_count + n_wildcards, second_count) {
+        (5, _) => HandType::FiveOfAKind,
+        (4, _) => HandType::FourOfAKind,
+        (3, 2) => HandType::FullHouse,
+        (3, _) => HandType::ThreeOfAKind,
+        (2, 2) => HandType::TwoPair,
+        (2, _) => HandType::OnePair,
+        (1, _) => HandType::HighCard,
+        _ => panic!("Got unexpected counts. There must be a bug in this function."),
+    };
+
+    if rules.straights() && hand_type < HandType::Straight && is_straight(ranks, wildcards) {
+        HandType::Straight
+    } else {
+        hand_type
+    }
+}
+
+/// Whether the cards follow each other, once the wildcards fill the gaps.
+fn is_straight(ranks: &[u8; 5], wildcards: &[bool; 5]) -> bool {
+    if rank_counts(ranks, wildcards).iter().any(|&count| count > 1) {
+        return false;
+    }
+
+    let mut non_wild = ranks
+        .iter()
+        .zip(wildcards)
+        .filter(|(_, &wildcard)| !wildcard)
+        .map(|(&rank, _)| rank);
+    let Some(first) = non_wild.next() else {
+        return true;
+    };
+    let (min, max) = non_wild.fold((first, first), |(min, max), rank| {
+        (min.min(rank), max.max(rank))
+    });
+
+    max - min < 5
+}
+
+/// The ranks of the cards, in the order they are compared in.
+fn tie_break_ranks(mut ranks: [u8; 5], tie_break: TieBreak) -> [u8; 5] {
+    if tie_break == TieBreak::ByGroup {
+        let counts = rank_counts(&ranks, &[false; 5]);
+        ranks.sort_unstable_by_key(|&rank| std::cmp::Reverse((counts[rank as usize], rank)));
+    }
+
+    ranks
+}
+
+type Bid = u64;
+
+/// What a bid wins for a hand of rank `rank`, 1 for the weakest hand, which
+/// always fits in a u128.
+pub fn winnings(rank: usize, bid: Bid) -> u128 {
+    rank as u128 * bid as u128
+}
+
+/// The sum of the bids, each multiplied by the rank of its hand, the hands
+/// being given by their keys, or `None` if it does not fit in a u128.
+pub fn total_winnings(keyed_bids: impl IntoIterator<Item = (u32, Bid)>) -> Option<u128> {
+    let mut keyed_bids: Vec<(u32, Bid)> = keyed_bids.into_iter().collect();
+    keyed_bids.sort_unstable();
+
+    keyed_bids
+        .iter()
+        .enumerate()
+        .try_fold(0u128, |total, (i, &(_, bid))| {
+            total.checked_add(winnings(i + 1, bid))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hand_type() {
+        let camel_cards = Ruleset::camel_cards();
+        let jokers = Ruleset::jokers();
+        let hand_type = |cards: &str, rules: &Ruleset| Hand::new(cards, rules).hand_type();
+
+        assert_eq!(hand_type("KTJJT", &camel_cards), HandType::TwoPair);
+        assert_eq!(hand_type("KTJJT", &jokers), HandType::FourOfAKind);
+        assert_eq!(hand_type("JJJJJ", &jokers), HandType::FiveOfAKind);
+        assert!(Hand::new("JKKK2", &camel_cards) > Hand::new("TKKK2", &camel_cards));
+        assert!(Hand::new("JKKK2", &jokers) < Hand::new("QQQQ2", &jokers));
+        assert!(Hand::new("JKKK2", &jokers) > Hand::new("TKKK2", &jokers));
+
+        // Several wildcards, and straights
+        let poker = Ruleset::new(crate::CARDS)
+            .with_wildcards("J2")
+            .with_tie_break(TieBreak::ByGroup)
+            .with_straights();
+        assert_eq!(hand_type("2JKQA", &poker), HandType::Straight);
+        assert_eq!(hand_type("3456J", &poker), HandType::Straight);
+        assert_eq!(hand_type("3457J", &poker), HandType::Straight);
+        assert_eq!(hand_type("3458J", &poker), HandType::OnePair);
+        assert_eq!(hand_type("2JKKA", &poker), HandType::FourOfAKind);
+        assert_eq!(hand_type("34567", &camel_cards), HandType::HighCard);
+
+        assert_eq!(
+            Hand::new("2JKQA", &poker).cards(),
+            &['2', 'J', 'K', 'Q', 'A']
+        );
+
+        // Pairs first, whatever their place in the hand
+        assert!(Hand::new("A3399", &poker) < Hand::new("4499A", &poker));
+        assert!(Hand::new("A3399", &camel_cards) > Hand::new("4499A", &camel_cards));
+    }
+
+    #[test]
+    fn test_key() {
+        let rules = Ruleset::camel_cards();
+        let mut hands: Vec<Hand> = ["QQQJA", "T55J5", "KK677", "32T3K", "KTJJT"]
+            .iter()
+            .map(|cards| Hand::new(cards, &rules))
+            .collect();
+        hands.sort_unstable_by_key(|hand| hand.key());
+        let cards: Vec<String> = hands
+            .iter()
+            .map(|hand| hand.cards().iter().collect())
+            .collect();
+        assert_eq!(cards, ["32T3K", "KTJJT", "KK677", "T55J5", "QQQJA"]);
+
+        // One pair, then the ranks of 3, 2, T, 3 and K
+        assert_eq!(Hand::new("32T3K", &rules).key(), 0x0011_081B);
+
+        let keyed_bids = hands.iter().zip([765, 220, 28, 684, 483]);
+        let keyed_bids = keyed_bids.map(|(hand, bid)| (hand.key(), bid));
+        assert_eq!(
+            total_winnings(keyed_bids),
+            Some(765 + 440 + 84 + 2736 + 2415)
+        );
+
+        // Bids fit in a u64, but not their winnings
+        let keyed_bids = [(hands[1].key(), 2), (hands[0].key(), u64::MAX)];
+        assert_eq!(total_winnings(keyed_bids), Some(u64::MAX as u128 + 4));
+    }
+}
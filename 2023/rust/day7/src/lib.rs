@@ -0,0 +1,101 @@
+use common::{ParseError, Solution, SolveError};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use hand::{total_winnings, Hand};
+use report::Report;
+use ruleset::Ruleset;
+
+pub mod hand;
+pub mod report;
+pub mod ruleset;
+
+lazy_static! {
+    static ref LINE_RE: Regex = Regex::new(r"^(?<hand>\w{5}) (?<bid>\d+)$").unwrap();
+}
+
+/// The cards a hand can be made of, whatever the rules.
+const CARDS: &str = "23456789TJQKA";
+
+/// A line of the input, with the key of its hand under the rules of each part,
+/// so that solving a part only takes sorting keys.
+#[derive(Debug, Clone)]
+pub struct HandBid {
+    hand: String,
+    bid: u64,
+    /// See [`Hand::key`], with [`Ruleset::camel_cards`].
+    camel_cards_key: u32,
+    /// The same, with [`Ruleset::jokers`].
+    jokers_key: u32,
+}
+
+/// Why there is no total, with so many hands and such big bids.
+const WINNINGS_OVERFLOW: &str = "The total winnings do not fit in a u128.";
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Parsed = Vec<HandBid>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let camel_cards = Ruleset::camel_cards();
+        let jokers = Ruleset::jokers();
+
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let cap = LINE_RE.captures(line).ok_or_else(|| {
+                    ParseError::line(Self::DAY, i, line, "a hand and a bid, like '32T3K 765'")
+                })?;
+
+                let hand = cap.name("hand").unwrap();
+                if let Some((col, c)) = hand
+                    .as_str()
+                    .char_indices()
+                    .find(|&(_, c)| !CARDS.contains(c))
+                {
+                    let start = hand.start() + col;
+                    return Err(ParseError::new(
+                        Self::DAY,
+                        i,
+                        line,
+                        start..start + c.len_utf8(),
+                        format!("a card, one of {CARDS}"),
+                    ));
+                }
+
+                let bid = cap.name("bid").unwrap();
+                let bid: u64 = ParseError::number(Self::DAY, i, line, bid.range())?;
+                Ok(HandBid {
+                    hand: hand.as_str().to_string(),
+                    bid,
+                    camel_cards_key: Hand::new(hand.as_str(), &camel_cards).key(),
+                    jokers_key: Hand::new(hand.as_str(), &jokers).key(),
+                })
+            })
+            .collect()
+    }
+
+    fn solve_part1(handbids: &Self::Parsed) -> Result<Self::Answer1, SolveError> {
+        total_winnings(handbids.iter().map(|hb| (hb.camel_cards_key, hb.bid)))
+            .ok_or_else(|| SolveError::new(Self::DAY, 1, WINNINGS_OVERFLOW))
+    }
+
+    fn solve_part2(handbids: &Self::Parsed) -> Result<Self::Answer2, SolveError> {
+        total_winnings(handbids.iter().map(|hb| (hb.jokers_key, hb.bid)))
+            .ok_or_else(|| SolveError::new(Self::DAY, 2, WINNINGS_OVERFLOW))
+    }
+
+    fn explain(handbids: &Self::Parsed, part: u8) -> Option<String> {
+        let rules = match part {
+            1 => Ruleset::camel_cards(),
+            _ => Ruleset::jokers(),
+        };
+        Some(Report::new(handbids, &rules).to_string())
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+
+use crate::{
+    hand::{winnings, Hand, HandType},
+    ruleset::Ruleset,
+    HandBid,
+};
+
+/// How one hand fared.
+#[derive(Debug, Clone)]
+pub struct HandReport {
+    pub hand: Hand,
+    pub hand_type: HandType,
+    /// 1 for the weakest hand.
+    pub rank: u64,
+    pub bid: u64,
+    pub winnings: u128,
+    /// The card the wildcards stand for, see [`Hand::promotion`].
+    pub promotion: Option<char>,
+}
+
+/// How all the hands fared, from the weakest to the strongest.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub hands: Vec<HandReport>,
+    /// The number of hands of each type, from the weakest type.
+    pub hand_type_counts: Vec<(HandType, usize)>,
+    /// `None` if it does not fit in a u128.
+    pub total_winnings: Option<u128>,
+}
+
+impl Report {
+    pub fn new(handbids: &[HandBid], rules: &Ruleset) -> Report {
+        let mut hands: Vec<(Hand, u64)> = handbids
+            .iter()
+            .map(|hb| (Hand::new(&hb.hand, rules), hb.bid))
+            .collect();
+        hands.sort_unstable_by_key(|(hand, _)| hand.key());
+
+        let hands: Vec<HandReport> = hands
+            .into_iter()
+            .enumerate()
+            .map(|(i, (hand, bid))| HandReport {
+                hand_type: hand.hand_type(),
+                rank: i as u64 + 1,
+                bid,
+                winnings: winnings(i + 1, bid),
+                promotion: hand.promotion(rules),
+                hand,
+            })
+            .collect();
+
+        // Hands are sorted, so hands of a type follow each other
+        let mut hand_type_counts: Vec<(HandType, usize)> = vec![];
+        for hand in &hands {
+            match hand_type_counts.last_mut() {
+                Some((hand_type, count)) if *hand_type == hand.hand_type => *count += 1,
+                _ => hand_type_counts.push((hand.hand_type, 1)),
+            }
+        }
+
+        Report {
+            total_winnings: hands
+                .iter()
+                .try_fold(0u128, |total, hand| total.checked_add(hand.winnings)),
+            hands,
+            hand_type_counts,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Hand types:")?;
+        for (hand_type, count) in &self.hand_type_counts {
+            writeln!(f, "  {hand_type:<15} {count:>6}")?;
+        }
+
+        writeln!(
+            f,
+            "{:>6}  {:<5}  {:<15}  {:>6}  {:>12}  Wildcards",
+            "Rank", "Hand", "Type", "Bid", "Winnings"
+        )?;
+        for hand in &self.hands {
+            let cards: String = hand.hand.cards().iter().collect();
+            write!(
+                f,
+                "{:>6}  {cards:<5}  {:<15}  {:>6}  {:>12}",
+                hand.rank, hand.hand_type, hand.bid, hand.winnings
+            )?;
+            match hand.promotion {
+                Some(card) => writeln!(f, "  as {card}")?,
+                None => writeln!(f)?,
+            }
+        }
+
+        match self.total_winnings {
+            Some(total) => write!(f, "Total winnings: {total}"),
+            None => write!(f, "Total winnings: more than fit in a u128"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::*;
+    use crate::Day7;
+
+    #[test]
+    fn test_report() {
+        let handbids = Day7::parse(include_str!("example.txt")).unwrap();
+        let report = Report::new(&handbids, &Ruleset::jokers());
+
+        assert_eq!(report.total_winnings, Some(5905));
+        assert_eq!(
+            report.hand_type_counts,
+            [
+                (HandType::OnePair, 1),
+                (HandType::TwoPair, 1),
+                (HandType::FourOfAKind, 3)
+            ]
+        );
+        let promotions: Vec<(String, Option<char>)> = report
+            .hands
+            .iter()
+            .map(|hand| (hand.hand.cards().iter().collect(), hand.promotion))
+            .collect();
+        assert_eq!(
+            promotions,
+            [
+                ("32T3K".to_string(), None),
+                ("KK677".to_string(), None),
+                ("T55J5".to_string(), Some('5')),
+                ("QQQJA".to_string(), Some('Q')),
+                ("KTJJT".to_string(), Some('T')),
+            ]
+        );
+
+        let jokers = Ruleset::jokers();
+        assert_eq!(Hand::new("JJJJJ", &jokers).promotion(&jokers), Some('A'));
+        assert_eq!(Hand::new("JK2K2", &jokers).promotion(&jokers), Some('K'));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::CARDS;
+
+/// How hands of the same type are told apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Compare the cards one by one, in the order they were dealt.
+    FirstCard,
+    /// Compare the cards from the most to the least repeated, the strongest
+    /// first among as repeated ones, as in poker.
+    ByGroup,
+}
+
+/// The rules giving a meaning to the cards of a hand.
+#[derive(Debug, Clone)]
+pub struct Ruleset {
+    /// All the cards, from the weakest to the strongest.
+    order: Vec<char>,
+    /// Cards standing for whichever card makes the strongest hand type.
+    wildcards: Vec<char>,
+    tie_break: TieBreak,
+    /// Whether five cards following each other in `order` make a straight.
+    straights: bool,
+}
+
+impl Ruleset {
+    /// `order` lists the cards from the weakest to the strongest.
+    pub fn new(order: &str) -> Ruleset {
+        let order: Vec<char> = order.chars().collect();
+        assert!(
+            order.len() <= 16,
+            "There can be at most 16 cards, for their ranks to fit in 4 bits, but got {}.",
+            order.len()
+        );
+        assert!(
+            CARDS.chars().all(|card| order.contains(&card)),
+            "The order of the cards must contain all of {CARDS}, but got {}.",
+            order.iter().collect::<String>()
+        );
+
+        Ruleset {
+            order,
+            wildcards: vec![],
+            tie_break: TieBreak::FirstCard,
+            straights: false,
+        }
+    }
+
+    /// The rules of part 1.
+    pub fn camel_cards() -> Ruleset {
+        Ruleset::new(CARDS)
+    }
+
+    /// The rules of part 2, where `J` is the weakest card but can be any other.
+    pub fn jokers() -> Ruleset {
+        Ruleset::new("J23456789TQKA").with_wildcards("J")
+    }
+
+    pub fn with_wildcards(mut self, wildcards: &str) -> Ruleset {
+        self.wildcards = wildcards.chars().collect();
+        self
+    }
+
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Ruleset {
+        self.tie_break = tie_break;
+        self
+    }
+
+    pub fn with_straights(mut self) -> Ruleset {
+        self.straights = true;
+        self
+    }
+
+    /// The strength of `card`, 0 being the weakest.
+    pub fn rank(&self, card: char) -> u8 {
+        self.order
+            .iter()
+            .position(|&c| c == card)
+            .expect("Cards are checked when parsing.") as u8
+    }
+
+    /// The strongest card that is not a wildcard, if any.
+    pub fn strongest_card(&self) -> Option<char> {
+        self.order
+            .iter()
+            .rev()
+            .find(|&&card| !self.is_wildcard(card))
+            .copied()
+    }
+
+    pub fn is_wildcard(&self, card: char) -> bool {
+        self.wildcards.contains(&card)
+    }
+
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    pub fn straights(&self) -> bool {
+        self.straights
+    }
+}